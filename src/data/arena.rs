@@ -299,6 +299,60 @@ impl<T> Arena<T> {
         }
     }
 
+    /// Takes a slot out of the free list, allocating more capacity if necessary, and returns
+    /// the index of the element that will be inserted into it with [`Self::insert_reserved`].
+    ///
+    /// Until it is filled, the slot is considered neither occupied nor free: it is skipped by
+    /// iterators and it is not reused by other insertions.
+    pub(crate) fn reserve_slot(&mut self) -> Index {
+        if self.free_list_head.is_none() {
+            let len = self.items.len();
+            self.reserve(len.max(1));
+        }
+
+        let index = self
+            .try_alloc_next_index()
+            .expect("allocating will always succeed after reserving additional space");
+        // The slot doesn't contain any element yet.
+        self.len -= 1;
+        index
+    }
+
+    /// Inserts `value` into a slot previously returned by [`Self::reserve_slot`].
+    ///
+    /// The caller must ensure that `i` was returned by `reserve_slot` and wasn't filled since,
+    /// otherwise the free list would be corrupted.
+    pub(crate) fn insert_reserved(&mut self, i: Index, value: T) {
+        let entry = &mut self.items[i.index as usize];
+        assert!(
+            matches!(entry, Entry::Free { .. }),
+            "the reserved slot is already occupied"
+        );
+        *entry = Entry::Occupied {
+            generation: i.generation,
+            value,
+        };
+        self.len += 1;
+    }
+
+    /// Puts a slot previously returned by [`Self::reserve_slot`], and not filled since, back
+    /// into the free list so it can be reused by other insertions.
+    ///
+    /// The generation is incremented, like when an element is removed, so the index
+    /// returned by `reserve_slot` can't refer to anything inserted into this slot later.
+    pub(crate) fn release_reserved(&mut self, i: Index) {
+        let entry = &mut self.items[i.index as usize];
+        assert!(
+            matches!(entry, Entry::Free { .. }),
+            "the reserved slot is already occupied"
+        );
+        *entry = Entry::Free {
+            next_free: self.free_list_head,
+        };
+        self.generation += 1;
+        self.free_list_head = Some(i.index);
+    }
+
     /// Insert the value returned by `create` into the arena, allocating more capacity if necessary.
     /// `create` is called with the new value's associated index, allowing values that know their own index.
     ///
@@ -570,6 +624,65 @@ impl<T> Arena<T> {
         (item1, item2)
     }
 
+    /// Get mutable references to the `N` elements at the given indices.
+    ///
+    /// Returns `None` if any of the elements is not in the arena, or if two of the indices
+    /// point to the same item of the arena.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use rapier::data::arena::Arena;
+    ///
+    /// let mut arena = Arena::new();
+    /// let idx1 = arena.insert(0);
+    /// let idx2 = arena.insert(1);
+    /// let idx3 = arena.insert(2);
+    ///
+    /// {
+    ///     let [item3, item1] = arena.get_many_mut([idx3, idx1]).unwrap();
+    ///     *item3 = 4;
+    ///     *item1 = 5;
+    /// }
+    ///
+    /// assert_eq!(arena[idx1], 5);
+    /// assert_eq!(arena[idx3], 4);
+    /// assert!(arena.get_many_mut([idx2, idx2]).is_none());
+    /// ```
+    pub fn get_many_mut<const N: usize>(&mut self, indices: [Index; N]) -> Option<[&mut T; N]> {
+        for (k, i) in indices.iter().enumerate() {
+            if !self.contains(*i) || indices[..k].iter().any(|j| j.index == i.index) {
+                return None;
+            }
+        }
+
+        // Visit the items by increasing index so they can be split off the slice one by one.
+        let mut order = [0; N];
+        for (k, o) in order.iter_mut().enumerate() {
+            *o = k;
+        }
+        order.sort_unstable_by_key(|k| indices[*k].index);
+
+        let mut result = [(); N].map(|_| None);
+        let mut rest = &mut self.items[..];
+        let mut offset = 0;
+
+        for k in order {
+            let index = indices[k].index as usize;
+            let (item, tail) = std::mem::take(&mut rest)[index - offset..]
+                .split_first_mut()
+                .unwrap();
+            result[k] = match item {
+                Entry::Occupied { value, .. } => Some(value),
+                Entry::Free { .. } => unreachable!(),
+            };
+            rest = tail;
+            offset = index + 1;
+        }
+
+        Some(result.map(|item| item.unwrap()))
+    }
+
     /// Get the length of this arena.
     ///
     /// The length is the number of elements the arena holds.
@@ -1188,3 +1301,66 @@ impl<T> ops::IndexMut<Index> for Arena<T> {
         self.get_mut(index).expect("No element at index")
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::Arena;
+
+    #[test]
+    fn reserved_slot_is_neither_occupied_nor_free() {
+        let mut arena = Arena::with_capacity(2);
+        let a = arena.insert(1);
+        let reserved = arena.reserve_slot();
+
+        assert_eq!(arena.len(), 1);
+        assert_eq!(arena.capacity(), 2);
+        assert_eq!(arena.get(reserved), None);
+        assert_eq!(arena.iter().map(|(i, _)| i).collect::<Vec<_>>(), vec![a]);
+
+        // The reserved slot isn't reused by other insertions.
+        let b = arena.insert(2);
+        assert_ne!(b.into_raw_parts().0, reserved.into_raw_parts().0);
+        assert_eq!(arena.capacity(), 4);
+
+        arena.insert_reserved(reserved, 3);
+        assert_eq!(arena.len(), 3);
+        assert_eq!(arena.get(reserved), Some(&3));
+    }
+
+    #[test]
+    fn reserve_slot_allocates_when_full() {
+        let mut arena = Arena::with_capacity(1);
+        let a = arena.insert(1);
+        let reserved = arena.reserve_slot();
+
+        assert_eq!(arena.capacity(), 2);
+        assert_eq!(arena.len(), 1);
+
+        arena.insert_reserved(reserved, 42);
+        assert_eq!(arena[a], 1);
+        assert_eq!(arena[reserved], 42);
+    }
+
+    #[test]
+    fn released_slot_is_reused_with_a_new_generation() {
+        let mut arena = Arena::with_capacity(1);
+        let reserved = arena.reserve_slot();
+        arena.release_reserved(reserved);
+
+        assert_eq!(arena.len(), 0);
+        let a = arena.insert(1);
+        assert_eq!(a.into_raw_parts().0, reserved.into_raw_parts().0);
+        assert_ne!(a, reserved);
+        assert_eq!(arena.get(reserved), None);
+        assert_eq!(arena.capacity(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "the reserved slot is already occupied")]
+    fn insert_reserved_twice_panics() {
+        let mut arena = Arena::new();
+        let reserved = arena.reserve_slot();
+        arena.insert_reserved(reserved, 1);
+        arena.insert_reserved(reserved, 2);
+    }
+}
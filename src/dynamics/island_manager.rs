@@ -1,65 +1,203 @@
 use crate::dynamics::{
-    ImpulseJointSet, MultibodyJointSet, RigidBodyActivation, RigidBodyColliders, RigidBodyHandle,
-    RigidBodyIds, RigidBodySet, RigidBodyType, RigidBodyVelocity,
+    ImpulseJointSet, MultibodyJointSet, RigidBody, RigidBodyActivation, RigidBodyColliders,
+    RigidBodyHandle, RigidBodyIds, RigidBodySet, RigidBodyType, RigidBodyVelocity,
 };
 use crate::geometry::{ColliderSet, NarrowPhase};
-use crate::math::Real;
+use crate::math::{Point, Real, Vector};
 use crate::utils::WDot;
+use bit_vec::BitVec;
+
+/// The number of active dynamic rigid-bodies kept awake by each reason, as computed by
+/// [`IslandManager::awake_breakdown`].
+///
+/// Each active dynamic rigid-body is counted once, by the first matching reason in the
+/// order of the fields of this structure.
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct AwakeBreakdown {
+    /// Rigid-bodies that moved too much to fall asleep.
+    pub by_energy: usize,
+    /// Rigid-bodies that could fall asleep but are kept awake by at least one lease.
+    pub by_lease: usize,
+    /// Rigid-bodies in contact with a moving kinematic rigid-body.
+    pub by_kinematic_contact: usize,
+    /// Rigid-bodies attached by a joint to another active dynamic rigid-body.
+    pub by_joint: usize,
+    /// Rigid-bodies in contact with another active dynamic rigid-body.
+    pub by_contact: usize,
+}
 
 /// Structure responsible for maintaining the set of active rigid-bodies, and
 /// putting non-moving rigid-bodies to sleep to save computation times.
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
-#[derive(Clone, Default)]
+#[derive(Clone)]
 pub struct IslandManager {
     pub(crate) active_dynamic_set: Vec<RigidBodyHandle>,
     pub(crate) active_kinematic_set: Vec<RigidBodyHandle>,
     pub(crate) active_islands: Vec<usize>,
     active_set_timestamp: u32,
+    // Minimum island size overriding the one from the integration parameters,
+    // and the number of active set updates it remains valid for.
+    min_island_size_override: Option<(usize, u32)>,
+    last_woken: Option<RigidBodyHandle>,
+    // Number of bodies of the active dynamic set that were not part of it after the previous update.
+    num_new_active_bodies: usize,
+    // Number of island boundaries created by the last active set traversal.
+    last_island_split_count: usize,
+    // Whether or not the active set updates track the island changes of each rigid-body.
+    track_island_changes: bool,
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    island_count_samples: Vec<usize>,
     #[cfg_attr(feature = "serde-serialize", serde(skip))]
     can_sleep: Vec<RigidBodyHandle>, // Workspace.
     #[cfg_attr(feature = "serde-serialize", serde(skip))]
     stack: Vec<RigidBodyHandle>, // Workspace.
 }
 
+impl Default for IslandManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl IslandManager {
+    /// The maximum number of samples kept by [`Self::push_island_count_sample`].
+    pub const MAX_ISLAND_COUNT_SAMPLES: usize = 256;
+
     /// Creates a new empty island manager.
     pub fn new() -> Self {
         Self {
             active_dynamic_set: vec![],
             active_kinematic_set: vec![],
-            active_islands: vec![],
+            active_islands: vec![0],
             active_set_timestamp: 0,
+            min_island_size_override: None,
+            last_woken: None,
+            num_new_active_bodies: 0,
+            last_island_split_count: 0,
+            track_island_changes: false,
+            island_count_samples: vec![],
             can_sleep: vec![],
             stack: vec![],
         }
     }
 
+    /// Creates a new empty island manager able to hold `active_dynamic` active dynamic
+    /// rigid-bodies and `active_kinematic` active kinematic rigid-bodies without reallocating.
+    ///
+    /// The workspaces used while updating the active set are reserved accordingly.
+    pub fn with_capacity(active_dynamic: usize, active_kinematic: usize) -> Self {
+        Self {
+            active_dynamic_set: Vec::with_capacity(active_dynamic),
+            active_kinematic_set: Vec::with_capacity(active_kinematic),
+            can_sleep: Vec::with_capacity(active_dynamic),
+            stack: Vec::with_capacity(active_dynamic),
+            ..Self::new()
+        }
+    }
+
     pub(crate) fn num_islands(&self) -> usize {
-        self.active_islands.len() - 1
+        // NOTE: use a saturating subtraction in case the active islands come from
+        //       a deserialized island manager that was never updated.
+        self.active_islands.len().saturating_sub(1)
+    }
+
+    /// The number of active islands computed during the last timestep.
+    pub fn num_active_islands(&self) -> usize {
+        self.num_islands()
+    }
+
+    /// The number of times the active set was updated so far.
+    ///
+    /// This is incremented once per timestep (or once per CCD substep).
+    pub fn active_set_timestamp(&self) -> u32 {
+        self.active_set_timestamp
+    }
+
+    /// Records the current number of active islands.
+    ///
+    /// Only the last [`Self::MAX_ISLAND_COUNT_SAMPLES`] samples are kept, older samples being
+    /// discarded first.
+    pub fn push_island_count_sample(&mut self) {
+        let num_islands = self.num_islands();
+
+        if self.island_count_samples.len() < Self::MAX_ISLAND_COUNT_SAMPLES {
+            self.island_count_samples.push(num_islands);
+        } else {
+            self.island_count_samples.rotate_left(1);
+            *self.island_count_samples.last_mut().unwrap() = num_islands;
+        }
+    }
+
+    /// The island counts recorded with [`Self::push_island_count_sample`], from the oldest
+    /// to the most recent.
+    pub fn island_count_samples(&self) -> &[usize] {
+        &self.island_count_samples
+    }
+
+    /// Overrides the minimum island size from the integration parameters during the next
+    /// `steps` updates of the active set.
+    ///
+    /// Larger islands can help keeping chaotic events (like explosions) coherent. Once the
+    /// countdown reaches zero, the minimum island size from [`RigidBodySet::set_min_island_size`]
+    /// or from the integration parameters is used again. Setting `steps` to zero cancels any
+    /// pending override.
+    pub fn set_min_island_size_for_steps(&mut self, size: usize, steps: u32) {
+        self.min_island_size_override = if steps > 0 { Some((size, steps)) } else { None };
+    }
+
+    /// Enables or disables the tracking of [`RigidBody::steps_since_island_change`] by the
+    /// active set updates (default: `false`).
+    ///
+    /// This costs an additional pass over the active dynamic rigid-bodies at each timestep.
+    pub fn set_track_island_changes(&mut self, enabled: bool) {
+        self.track_island_changes = enabled;
+    }
+
+    // Resets the active sets and islands after all the rigid-bodies were removed, keeping
+    // the allocated capacities.
+    pub(crate) fn clear_active_sets(&mut self) {
+        self.active_dynamic_set.clear();
+        self.active_kinematic_set.clear();
+        self.active_islands.clear();
+        self.active_islands.push(0);
+        self.active_set_timestamp = 0;
+        self.last_woken = None;
+        self.num_new_active_bodies = 0;
+        self.last_island_split_count = 0;
+        self.can_sleep.clear();
+        self.stack.clear();
     }
 
     /// Update this data-structure after one or multiple rigid-bodies have been removed for `bodies`.
     pub fn cleanup_removed_rigid_bodies(&mut self, bodies: &mut RigidBodySet) {
-        let mut active_sets = [&mut self.active_kinematic_set, &mut self.active_dynamic_set];
-
-        for active_set in &mut active_sets {
-            let mut i = 0;
-
-            while i < active_set.len() {
-                let handle = active_set[i];
-                if bodies.get(handle).is_none() {
-                    // This rigid-body no longer exists, so we need to remove it from the active set.
-                    active_set.swap_remove(i);
-
-                    if i < active_set.len() {
-                        // Update the active_set_id for the body that has been swapped.
-                        if let Some(swapped_rb) = bodies.get_mut_internal(active_set[i]) {
-                            swapped_rb.ids.active_set_id = i;
-                        }
+        let mut i = 0;
+
+        while i < self.active_kinematic_set.len() {
+            let handle = self.active_kinematic_set[i];
+            if bodies.get(handle).is_none() {
+                // This rigid-body no longer exists, so we need to remove it from the active set.
+                self.active_kinematic_set.swap_remove(i);
+
+                if i < self.active_kinematic_set.len() {
+                    // Update the active_set_id for the body that has been swapped.
+                    if let Some(swapped_rb) = bodies.get_mut_internal(self.active_kinematic_set[i])
+                    {
+                        swapped_rb.ids.active_set_id = i;
                     }
-                } else {
-                    i += 1;
                 }
+            } else {
+                i += 1;
+            }
+        }
+
+        let mut i = self.active_dynamic_set.len();
+
+        while i > 0 {
+            i -= 1;
+
+            if bodies.get(self.active_dynamic_set[i]).is_none() {
+                self.remove_from_active_dynamic_set(i, bodies);
             }
         }
     }
@@ -70,20 +208,215 @@ impl IslandManager {
         removed_ids: &RigidBodyIds,
         bodies: &mut RigidBodySet,
     ) {
-        let mut active_sets = [&mut self.active_kinematic_set, &mut self.active_dynamic_set];
+        self.remove_from_active_sets(removed_handle, removed_ids, bodies);
 
-        for active_set in &mut active_sets {
-            if active_set.get(removed_ids.active_set_id) == Some(&removed_handle) {
-                active_set.swap_remove(removed_ids.active_set_id);
+        if self.last_woken == Some(removed_handle) {
+            self.last_woken = None;
+        }
+    }
 
-                if let Some(replacement) = active_set
-                    .get(removed_ids.active_set_id)
-                    .and_then(|h| bodies.get_mut_internal(*h))
-                {
-                    replacement.ids.active_set_id = removed_ids.active_set_id;
+    // Removes the given rigid-bodies, already removed from `bodies`, from the active sets with
+    // a single compaction pass over each active set. Active islands are kept contiguous and in
+    // the same order, but may become empty.
+    pub(crate) fn rigid_bodies_removed(
+        &mut self,
+        removed: &[(RigidBodyHandle, RigidBodyIds)],
+        bodies: &mut RigidBodySet,
+    ) {
+        let mut removed_dynamic = vec![false; self.active_dynamic_set.len()];
+        let mut removed_kinematic = vec![false; self.active_kinematic_set.len()];
+
+        for (handle, ids) in removed {
+            if self.active_kinematic_set.get(ids.active_set_id) == Some(handle) {
+                removed_kinematic[ids.active_set_id] = true;
+            }
+
+            if self.active_dynamic_set.get(ids.active_set_id) == Some(handle) {
+                removed_dynamic[ids.active_set_id] = true;
+            }
+
+            if self.last_woken == Some(*handle) {
+                self.last_woken = None;
+            }
+        }
+
+        let mut kept = 0;
+        for (i, is_removed) in removed_kinematic.into_iter().enumerate() {
+            if !is_removed {
+                let handle = self.active_kinematic_set[i];
+                self.active_kinematic_set[kept] = handle;
+
+                if kept != i {
+                    if let Some(rb) = bodies.get_mut_internal(handle) {
+                        rb.ids.active_set_id = kept;
+                    }
+                }
+
+                kept += 1;
+            }
+        }
+        self.active_kinematic_set.truncate(kept);
+
+        let len = self.active_dynamic_set.len();
+        let mut kept = 0;
+        let mut next_boundary = 0;
+
+        for (i, is_removed) in removed_dynamic.into_iter().enumerate() {
+            // Move the boundaries of the islands starting at `i` to the compacted position.
+            while next_boundary < self.active_islands.len()
+                && self.active_islands[next_boundary].min(len) <= i
+            {
+                self.active_islands[next_boundary] = kept;
+                next_boundary += 1;
+            }
+
+            if !is_removed {
+                let handle = self.active_dynamic_set[i];
+                self.active_dynamic_set[kept] = handle;
+
+                if kept != i {
+                    let island_start = self.active_islands[next_boundary - 1];
+                    if let Some(rb) = bodies.get_mut_internal(handle) {
+                        rb.ids.active_set_id = kept;
+                        rb.ids.active_set_offset = kept - island_start;
+                    }
                 }
+
+                kept += 1;
             }
         }
+
+        for boundary in &mut self.active_islands[next_boundary..] {
+            *boundary = kept;
+        }
+        self.active_dynamic_set.truncate(kept);
+    }
+
+    fn remove_from_active_sets(
+        &mut self,
+        removed_handle: RigidBodyHandle,
+        removed_ids: &RigidBodyIds,
+        bodies: &mut RigidBodySet,
+    ) {
+        let active_set = &mut self.active_kinematic_set;
+
+        if active_set.get(removed_ids.active_set_id) == Some(&removed_handle) {
+            active_set.swap_remove(removed_ids.active_set_id);
+
+            if let Some(replacement) = active_set
+                .get(removed_ids.active_set_id)
+                .and_then(|h| bodies.get_mut_internal(*h))
+            {
+                replacement.ids.active_set_id = removed_ids.active_set_id;
+            }
+        }
+
+        if self.active_dynamic_set.get(removed_ids.active_set_id) == Some(&removed_handle) {
+            self.remove_from_active_dynamic_set(removed_ids.active_set_id, bodies);
+        }
+    }
+
+    // Removes the `i`-th element of the active dynamic set while keeping every active
+    // island contiguous: the removed element is moved to the end of the active set by
+    // swapping it with the last element of its island, then with the last element of the
+    // next island, and so on, shrinking each island boundary crossed by one.
+    fn remove_from_active_dynamic_set(&mut self, mut i: usize, bodies: &mut RigidBodySet) {
+        let len = self.active_dynamic_set.len();
+        let mut island_start = 0;
+
+        for boundary in &mut self.active_islands {
+            *boundary = (*boundary).min(len);
+
+            if *boundary > i {
+                let last = *boundary - 1;
+
+                if i != last {
+                    self.active_dynamic_set.swap(i, last);
+
+                    if let Some(moved) = bodies.get_mut_internal(self.active_dynamic_set[i]) {
+                        moved.ids.active_set_id = i;
+                        moved.ids.active_set_offset = i - island_start;
+                    }
+                }
+
+                i = last;
+                *boundary = last;
+            }
+
+            island_start = *boundary;
+        }
+
+        // The removed element is now after the last island boundary, with only the bodies
+        // added to the active set since the last update.
+        self.active_dynamic_set.swap_remove(i);
+
+        if let Some(replacement) = self
+            .active_dynamic_set
+            .get(i)
+            .and_then(|h| bodies.get_mut_internal(*h))
+        {
+            replacement.ids.active_set_id = i;
+        }
+    }
+
+    /// Puts to sleep or wakes up the dynamic rigid-bodies of `bodies` so their sleeping state
+    /// matches `bits`, as computed by [`RigidBodySet::sleeping_bitset`].
+    ///
+    /// Rigid-bodies put to sleep have their velocities set to zero and are removed from the
+    /// active set immediately. Rigid-bodies woken up are added back to the active set. Slots
+    /// outside of the bitset are considered awake. Non-dynamic rigid-bodies are left unchanged.
+    pub fn apply_sleeping_bitset(&mut self, bodies: &mut RigidBodySet, bits: &BitVec) {
+        let to_update: Vec<_> = bodies
+            .iter()
+            .filter(|(_, rb)| rb.is_dynamic())
+            .filter_map(|(handle, rb)| {
+                let sleeping = bits
+                    .get(handle.into_raw_parts().0 as usize)
+                    .unwrap_or(false);
+                if sleeping != rb.is_sleeping() {
+                    Some((handle, sleeping))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        for (handle, sleeping) in to_update {
+            if sleeping {
+                self.sleep(bodies, handle);
+            } else {
+                self.wake_up(bodies, handle, true);
+            }
+        }
+    }
+
+    /// Forces the specified rigid-body to sleep if it is dynamic.
+    ///
+    /// Its velocities are set to zero and it is removed from the active set immediately. It
+    /// will only be woken up by a subsequent timestep if it touches, or is attached to, an
+    /// awake rigid-body or a moving kinematic rigid-body.
+    pub fn sleep(&mut self, bodies: &mut RigidBodySet, handle: RigidBodyHandle) {
+        if bodies.get(handle).map(|rb| rb.body_type()) == Some(RigidBodyType::Dynamic) {
+            #[cfg(feature = "trace-activation")]
+            log::debug!(target: "rapier::activation", "{:?} put to sleep (cause: manual)", handle);
+
+            let timestep_count = bodies.timestep_count;
+            let rb = bodies.index_mut_internal(handle);
+            rb.sleep();
+            rb.update_asleep_steps(timestep_count);
+            let ids = rb.ids;
+            self.remove_from_active_sets(handle, &ids, bodies);
+        }
+    }
+
+    /// Forces the specified rigid-bodies to sleep.
+    ///
+    /// This is equivalent to calling [`Self::sleep`] on each of them: non-dynamic or invalid
+    /// handles are ignored.
+    pub fn sleep_many(&mut self, bodies: &mut RigidBodySet, handles: &[RigidBodyHandle]) {
+        for handle in handles {
+            self.sleep(bodies, *handle);
+        }
     }
 
     /// Forces the specified rigid-body to wake up if it is dynamic.
@@ -94,9 +427,23 @@ impl IslandManager {
         // NOTE: the use an Option here because there are many legitimate cases (like when
         //       deleting a joint attached to an already-removed body) where we could be
         //       attempting to wake-up a rigid-body that has already been deleted.
-        if bodies.get(handle).map(|rb| rb.body_type()) == Some(RigidBodyType::Dynamic) {
+        if bodies
+            .get(handle)
+            .map(|rb| rb.body_type() == RigidBodyType::Dynamic && rb.is_enabled())
+            == Some(true)
+        {
+            let timestep_count = bodies.timestep_count;
             let rb = bodies.index_mut_internal(handle);
+
+            if rb.activation.sleeping {
+                self.last_woken = Some(handle);
+
+                #[cfg(feature = "trace-activation")]
+                log::debug!(target: "rapier::activation", "{:?} woken up (cause: manual)", handle);
+            }
+
             rb.activation.wake_up(strong);
+            rb.update_asleep_steps(timestep_count);
 
             if self.active_dynamic_set.get(rb.ids.active_set_id) != Some(&handle) {
                 rb.ids.active_set_id = self.active_dynamic_set.len();
@@ -105,6 +452,55 @@ impl IslandManager {
         }
     }
 
+    /// Wakes up the given rigid-bodies, appending them to the active set in the given order.
+    ///
+    /// Waking the same handles in the same order always results in the same active set layout,
+    /// which keeps the simulation deterministic. Non-dynamic rigid-bodies, invalid handles, and
+    /// rigid-bodies that are already in the active set (including duplicates) are skipped.
+    pub fn wake_many_ordered(
+        &mut self,
+        bodies: &mut RigidBodySet,
+        handles: &[RigidBodyHandle],
+        strong: bool,
+    ) {
+        for handle in handles {
+            self.wake_up(bodies, *handle, strong);
+        }
+    }
+
+    /// The handle of the rigid-body that most recently transitioned from sleeping to awake.
+    ///
+    /// This accounts for rigid-bodies woken up explicitly with [`Self::wake_up`] as well as
+    /// rigid-bodies woken up by contacts or joints during a timestep. Returns `None` if no
+    /// rigid-body was woken up yet, or if the last woken rigid-body was removed since.
+    pub fn last_woken(&self) -> Option<RigidBodyHandle> {
+        self.last_woken
+    }
+
+    /// The number of times the last active set update started a new island while traversing
+    /// the interaction graph.
+    ///
+    /// A connected component smaller than the minimum island size from the integration
+    /// parameters is merged with the next one instead of starting a new island, so comparing
+    /// this count with the number of connected components reveals how much merging happened.
+    pub fn last_island_split_count(&self) -> usize {
+        self.last_island_split_count
+    }
+
+    /// The fraction of the active dynamic set that was not part of the active dynamic set
+    /// computed by the previous timestep.
+    ///
+    /// This is `1.0` if the membership of the active dynamic set changed entirely, and `0.0` if
+    /// it didn't change at all (or if the active dynamic set is empty). Rigid-bodies woken up
+    /// between two timesteps count as new until the next active set update.
+    pub fn active_set_churn(&self) -> Real {
+        if self.active_dynamic_set.is_empty() {
+            0.0
+        } else {
+            self.num_new_active_bodies as Real / self.active_dynamic_set.len() as Real
+        }
+    }
+
     /// Iter through all the active kinematic rigid-bodies on this set.
     pub fn active_kinematic_bodies(&self) -> &[RigidBodyHandle] {
         &self.active_kinematic_set[..]
@@ -115,11 +511,386 @@ impl IslandManager {
         &self.active_dynamic_set[..]
     }
 
+    /// The number of active dynamic rigid-bodies kept awake by each reason during the last
+    /// timestep.
+    ///
+    /// This is computed on each call by going through all the active dynamic rigid-bodies and
+    /// their contacts, so it is only accurate if the sets were not modified since that timestep.
+    pub fn awake_breakdown(
+        &self,
+        bodies: &RigidBodySet,
+        colliders: &ColliderSet,
+        narrow_phase: &NarrowPhase,
+        impulse_joints: &ImpulseJointSet,
+        multibody_joints: &MultibodyJointSet,
+    ) -> AwakeBreakdown {
+        let mut breakdown = AwakeBreakdown::default();
+        let timestamp = self.active_set_timestamp;
+        let is_active_dynamic = |h: RigidBodyHandle| {
+            bodies
+                .get(h)
+                .map(|rb| rb.is_dynamic() && rb.ids.active_set_timestamp == timestamp)
+                == Some(true)
+        };
+        let mut contacting_bodies = vec![];
+
+        for handle in &self.active_dynamic_set {
+            let rb = &bodies[*handle];
+
+            if rb.activation.time_since_can_sleep < RigidBodyActivation::default_time_until_sleep()
+            {
+                breakdown.by_energy += 1;
+                continue;
+            }
+
+            if rb.has_awake_leases() {
+                breakdown.by_lease += 1;
+                continue;
+            }
+
+            contacting_bodies.clear();
+            push_contacting_bodies(
+                &rb.colliders,
+                colliders,
+                narrow_phase,
+                &mut contacting_bodies,
+            );
+
+            if contacting_bodies.iter().any(|h| {
+                bodies
+                    .get(*h)
+                    .map(|rb| rb.is_kinematic() && !rb.vels.is_zero())
+                    == Some(true)
+            }) {
+                breakdown.by_kinematic_contact += 1;
+            } else if impulse_joints
+                .attached_joints(*handle)
+                .any(|(rb1, rb2, _, _)| {
+                    is_active_dynamic(crate::utils::select_other((rb1, rb2), *handle))
+                })
+                || multibody_joints
+                    .attached_bodies(*handle)
+                    .any(is_active_dynamic)
+            {
+                breakdown.by_joint += 1;
+            } else {
+                breakdown.by_contact += 1;
+            }
+        }
+
+        breakdown
+    }
+
+    /// The number of active dynamic rigid-bodies.
+    pub fn num_active_dynamic(&self) -> usize {
+        self.active_dynamic_set.len()
+    }
+
+    /// The number of sleeping dynamic rigid-bodies in `bodies`.
+    ///
+    /// Fixed and kinematic rigid-bodies are not counted. Sleeping rigid-bodies are not tracked
+    /// by the island manager, so this iterates through all the rigid-bodies of `bodies`.
+    pub fn num_sleeping(&self, bodies: &RigidBodySet) -> usize {
+        bodies
+            .iter()
+            .filter(|(_, rb)| rb.is_dynamic() && rb.is_sleeping())
+            .count()
+    }
+
+    /// The number of active dynamic rigid-bodies divided by the total number of rigid-bodies
+    /// in `bodies`.
+    ///
+    /// Returns zero if `bodies` is empty.
+    pub fn active_ratio(&self, bodies: &RigidBodySet) -> Real {
+        self.active_dynamic_set.len() as Real / bodies.len().max(1) as Real
+    }
+
+    /// Calls `f` on each active dynamic rigid-body, until `f` returns `false`.
+    ///
+    /// This doesn't allocate, and stops the iteration as soon as `f` returns `false`.
+    pub fn for_each_active_dynamic(
+        &self,
+        bodies: &RigidBodySet,
+        mut f: impl FnMut(RigidBodyHandle, &RigidBody) -> bool,
+    ) {
+        for handle in &self.active_dynamic_set {
+            if let Some(rb) = bodies.get(*handle) {
+                if !f(*handle, rb) {
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Iter through all the active kinematic rigid-bodies that have a non-zero velocity.
+    ///
+    /// These are the kinematic rigid-bodies able to wake up the dynamic rigid-bodies they touch.
+    pub fn iter_moving_kinematic<'a>(
+        &'a self,
+        bodies: &'a RigidBodySet,
+    ) -> impl Iterator<Item = (RigidBodyHandle, &'a RigidBody)> + 'a {
+        self.active_kinematic_set
+            .iter()
+            .filter_map(move |handle| Some((*handle, bodies.get(*handle)?)))
+            .filter(|(_, rb)| rb.is_moving())
+    }
+
+    /// Iter through the rigid-bodies involved in the velocity resolution of the next timestep.
+    ///
+    /// These are all the active dynamic rigid-bodies, in island order, followed by the moving
+    /// kinematic rigid-bodies with at least one active contact.
+    pub fn iter_solved_this_step<'a>(
+        &'a self,
+        bodies: &'a RigidBodySet,
+        narrow_phase: &'a NarrowPhase,
+    ) -> impl Iterator<Item = RigidBodyHandle> + 'a {
+        let kinematics = self
+            .iter_moving_kinematic(bodies)
+            .filter(move |(_, rb)| {
+                rb.colliders().iter().any(|co_handle| {
+                    narrow_phase
+                        .contacts_with(*co_handle)
+                        .any(|pair| pair.has_any_active_contact)
+                })
+            })
+            .map(|(handle, _)| handle);
+
+        self.active_dynamic_set.iter().copied().chain(kinematics)
+    }
+
+    /// The handles of all the active dynamic rigid-bodies with a linear speed greater than
+    /// `speed_threshold`.
+    pub fn fast_moving_bodies(
+        &self,
+        bodies: &RigidBodySet,
+        speed_threshold: Real,
+    ) -> Vec<RigidBodyHandle> {
+        let sq_threshold = speed_threshold * speed_threshold;
+        self.active_dynamic_set
+            .iter()
+            .copied()
+            .filter(|handle| {
+                bodies
+                    .get(*handle)
+                    .map(|rb| rb.linvel().norm_squared() > sq_threshold)
+                    .unwrap_or(false)
+            })
+            .collect()
+    }
+
     pub(crate) fn active_island(&self, island_id: usize) -> &[RigidBodyHandle] {
-        let island_range = self.active_islands[island_id]..self.active_islands[island_id + 1];
+        // NOTE: clamp the island boundaries in case they come from a deserialized island
+        //       manager whose active set is out of sync with them.
+        let len = self.active_dynamic_set.len();
+        let island_range =
+            self.active_islands[island_id].min(len)..self.active_islands[island_id + 1].min(len);
         &self.active_dynamic_set[island_range]
     }
 
+    /// The total kinetic energy of each active island.
+    ///
+    /// The returned vector contains one entry per island, in the same order as the islands
+    /// computed during the last timestep.
+    pub fn island_energies(&self, bodies: &RigidBodySet) -> Vec<Real> {
+        (0..self.num_islands())
+            .map(|island_id| {
+                self.active_island(island_id)
+                    .iter()
+                    .filter_map(|handle| bodies.get(*handle))
+                    .map(|rb| rb.kinetic_energy())
+                    .sum()
+            })
+            .collect()
+    }
+
+    /// The ids of the active islands where every rigid-body moves slower than its sleep thresholds.
+    ///
+    /// The rigid-bodies of these islands are all on their way to fall asleep. Islands whose
+    /// rigid-bodies were all removed or put to sleep since the last timestep are not included.
+    pub fn settling_islands(&self, bodies: &RigidBodySet) -> Vec<usize> {
+        (0..self.num_islands())
+            .filter(|island_id| {
+                let island = self.active_island(*island_id);
+                !island.is_empty()
+                    && island.iter().all(|handle| {
+                        let rb = &bodies[*handle];
+                        let sq_linvel = rb.vels.linvel.norm_squared();
+                        let sq_angvel = rb.vels.angvel.gdot(rb.vels.angvel);
+                        is_below_sleep_thresholds(&rb.activation, sq_linvel, sq_angvel)
+                    })
+            })
+            .collect()
+    }
+
+    /// The number of contact manifolds with at least one solver contact in each active island.
+    ///
+    /// A contact manifold between two rigid-bodies of the same island is counted only once.
+    /// Contact manifolds between a rigid-body of an island and a non-dynamic rigid-body are
+    /// counted in the island of the dynamic rigid-body.
+    pub fn island_contact_counts(
+        &self,
+        bodies: &RigidBodySet,
+        narrow_phase: &NarrowPhase,
+    ) -> Vec<usize> {
+        let mut counts = vec![0; self.num_islands()];
+
+        for pair in narrow_phase.contact_pairs() {
+            for manifold in &pair.manifolds {
+                if manifold.data.solver_contacts.is_empty() {
+                    continue;
+                }
+
+                let island_id = manifold
+                    .data
+                    .rigid_body1
+                    .and_then(|h| self.island_of(bodies, h))
+                    .or_else(|| {
+                        manifold
+                            .data
+                            .rigid_body2
+                            .and_then(|h| self.island_of(bodies, h))
+                    });
+
+                if let Some(island_id) = island_id {
+                    counts[island_id] += 1;
+                }
+            }
+        }
+
+        counts
+    }
+
+    /// A rough estimate of the amount of work the constraints solver will perform during
+    /// the next timestep.
+    ///
+    /// This combines the number of active dynamic rigid-bodies, the number of contact manifolds
+    /// counted by [`Self::island_contact_counts`], and the number of joints attached to active
+    /// dynamic rigid-bodies into a single score. The score has no unit and isn't precise, but it
+    /// grows monotonically with each of these quantities. It is computed in `O(a + c)` time where
+    /// `a` is the number of active rigid-bodies and `c` the number of contact pairs.
+    pub fn estimated_solve_cost(
+        &self,
+        bodies: &RigidBodySet,
+        narrow_phase: &NarrowPhase,
+        impulse_joints: &ImpulseJointSet,
+        multibody_joints: &MultibodyJointSet,
+    ) -> u64 {
+        // Relative costs of one body, one contact manifold, and one joint.
+        const BODY_COST: u64 = 1;
+        const CONTACT_COST: u64 = 4;
+        const JOINT_COST: u64 = 4;
+
+        let num_bodies = self.active_dynamic_set.len() as u64;
+        let num_contacts: usize = self
+            .island_contact_counts(bodies, narrow_phase)
+            .iter()
+            .sum();
+        let num_joints: usize = self
+            .active_dynamic_set
+            .iter()
+            .map(|h| {
+                impulse_joints.attached_joints(*h).count()
+                    + multibody_joints.rigid_body_link(*h).is_some() as usize
+            })
+            .sum();
+
+        num_bodies * BODY_COST + num_contacts as u64 * CONTACT_COST + num_joints as u64 * JOINT_COST
+    }
+
+    /// The id of the active island containing the given rigid-body.
+    ///
+    /// Returns `None` if the rigid-body doesn't exist, or isn't a dynamic rigid-body that was
+    /// awake during the last timestep. Island ids are only meaningful until the next timestep.
+    pub fn island_of(&self, bodies: &RigidBodySet, handle: RigidBodyHandle) -> Option<usize> {
+        let ids = &bodies.get(handle)?.ids;
+
+        if ids.active_island_id < self.num_islands()
+            && self.active_dynamic_set.get(ids.active_set_id) == Some(&handle)
+            && self.active_islands[ids.active_island_id] <= ids.active_set_id
+            && ids.active_set_id < self.active_islands[ids.active_island_id + 1]
+        {
+            Some(ids.active_island_id)
+        } else {
+            None
+        }
+    }
+
+    /// The mean linear velocity of the rigid-bodies of each active island.
+    ///
+    /// The returned vector is indexed by island id. Empty islands, e.g., islands whose rigid-bodies
+    /// were all removed or put to sleep since the last timestep, have a zero mean velocity.
+    pub fn island_mean_velocity(&self, bodies: &RigidBodySet) -> Vec<Vector<Real>> {
+        (0..self.num_islands())
+            .map(|island_id| {
+                let island = self.active_island(island_id);
+                if island.is_empty() {
+                    return Vector::zeros();
+                }
+
+                island
+                    .iter()
+                    .map(|handle| bodies[*handle].vels.linvel)
+                    .sum::<Vector<Real>>()
+                    / island.len() as Real
+            })
+            .collect()
+    }
+
+    /// The average position of the rigid-bodies of the given active island, and the greatest
+    /// distance between this average and one of these rigid-bodies.
+    ///
+    /// Returns `None` if `island_id` does not identify an active island, or if all the
+    /// rigid-bodies of this island were removed or put to sleep since the last timestep.
+    pub fn island_centroid(
+        &self,
+        bodies: &RigidBodySet,
+        island_id: usize,
+    ) -> Option<(Point<Real>, Real)> {
+        if island_id >= self.num_islands() {
+            return None;
+        }
+
+        let island = self.active_island(island_id);
+        if island.is_empty() {
+            return None;
+        }
+
+        let centroid = island
+            .iter()
+            .map(|handle| bodies[*handle].translation())
+            .sum::<Vector<Real>>()
+            / island.len() as Real;
+        let radius = island
+            .iter()
+            .map(|handle| (bodies[*handle].translation() - centroid).norm())
+            .fold(0.0, Real::max);
+
+        Some((Point::from(centroid), radius))
+    }
+
+    /// Adds `delta` to the linear velocity of every rigid-body of the given active island.
+    ///
+    /// The rigid-bodies are strongly woken up so they remain awake during the next timesteps.
+    /// This does nothing if `island_id` does not identify an active island. Rigid-bodies removed
+    /// from the active set since the last timestep are no longer part of their island.
+    pub fn apply_island_velocity(
+        &self,
+        bodies: &mut RigidBodySet,
+        island_id: usize,
+        delta: Vector<Real>,
+    ) {
+        if island_id >= self.num_islands() {
+            return;
+        }
+
+        for handle in self.active_island(island_id) {
+            if let Some(rb) = bodies.get_mut_internal_with_modification_tracking(*handle) {
+                let linvel = rb.vels.linvel + delta;
+                rb.set_linvel(linvel, true);
+            }
+        }
+    }
+
     #[inline(always)]
     pub(crate) fn iter_active_bodies<'a>(&'a self) -> impl Iterator<Item = RigidBodyHandle> + 'a {
         self.active_dynamic_set
@@ -133,6 +904,46 @@ impl IslandManager {
         self.active_islands[island_id]..self.active_islands[island_id + 1]
     }
 
+    /// Computes, without modifying anything, how the active dynamic rigid-bodies would be
+    /// partitioned at the beginning of the next active set update.
+    ///
+    /// Returns the rigid-bodies that are candidates for sleeping, and the rigid-bodies seeding the
+    /// interaction graph traversal: the active dynamic rigid-bodies that must stay awake, followed
+    /// by the rigid-bodies touched by a moving kinematic rigid-body. During the traversal, the
+    /// seeds wake up every sleep candidate they are connected to by contacts or joints.
+    pub fn compute_active_set_selection(
+        &self,
+        dt: Real,
+        bodies: &RigidBodySet,
+        colliders: &ColliderSet,
+        narrow_phase: &NarrowPhase,
+    ) -> (Vec<RigidBodyHandle>, Vec<RigidBodyHandle>) {
+        let mut can_sleep = vec![];
+        let mut stack = vec![];
+
+        // NOTE: the `.rev()` matches the order of the actual active set update.
+        for h in self.active_dynamic_set.iter().rev() {
+            let rb = &bodies[*h];
+            let sq_linvel = rb.vels.linvel.norm_squared();
+            let sq_angvel = rb.vels.angvel.gdot(rb.vels.angvel);
+
+            if updated_time_since_can_sleep(&rb.activation, sq_linvel, sq_angvel, dt)
+                >= RigidBodyActivation::default_time_until_sleep()
+                && !rb.has_awake_leases()
+            {
+                can_sleep.push(*h);
+            } else {
+                stack.push(*h);
+            }
+        }
+
+        for (_, rb) in self.iter_moving_kinematic(bodies) {
+            push_contacting_bodies(&rb.colliders, colliders, narrow_phase, &mut stack);
+        }
+
+        (can_sleep, stack)
+    }
+
     pub(crate) fn update_active_set_with_contacts(
         &mut self,
         dt: Real,
@@ -141,17 +952,35 @@ impl IslandManager {
         narrow_phase: &NarrowPhase,
         impulse_joints: &ImpulseJointSet,
         multibody_joints: &MultibodyJointSet,
-        min_island_size: usize,
+        mut min_island_size: usize,
     ) {
+        if let Some(size) = bodies.min_island_size() {
+            min_island_size = size;
+        }
+
+        if let Some((size, remaining_steps)) = &mut self.min_island_size_override {
+            min_island_size = *size;
+            *remaining_steps -= 1;
+
+            if *remaining_steps == 0 {
+                self.min_island_size_override = None;
+            }
+        }
+
         assert!(
             min_island_size > 0,
             "The minimum island size must be at least 1."
         );
 
+        let timestep_count = bodies.timestep_count;
+
         // Update the energy of every rigid body and
         // keep only those that may not sleep.
         //        let t = instant::now();
+        let prev_timestamp = self.active_set_timestamp;
         self.active_set_timestamp += 1;
+        self.num_new_active_bodies = 0;
+        self.last_island_split_count = 0;
         self.stack.clear();
         self.can_sleep.clear();
 
@@ -170,43 +999,16 @@ impl IslandManager {
             update_energy(&mut rb.activation, sq_linvel, sq_angvel, dt);
 
             if rb.activation.time_since_can_sleep >= RigidBodyActivation::default_time_until_sleep()
+                && !rb.has_awake_leases()
             {
-                // Mark them as sleeping for now. This will
-                // be set to false during the graph traversal
-                // if it should not be put to sleep.
-                rb.activation.sleeping = true;
+                // These may be put to sleep. They will be kept
+                // awake if they are reached during the graph traversal.
                 can_sleep.push(h);
             } else {
                 stack.push(h);
             }
         }
 
-        // Read all the contacts and push objects touching touching this rigid-body.
-        #[inline(always)]
-        fn push_contacting_bodies(
-            rb_colliders: &RigidBodyColliders,
-            colliders: &ColliderSet,
-            narrow_phase: &NarrowPhase,
-            stack: &mut Vec<RigidBodyHandle>,
-        ) {
-            for collider_handle in &rb_colliders.0 {
-                for inter in narrow_phase.contacts_with(*collider_handle) {
-                    for manifold in &inter.manifolds {
-                        if !manifold.data.solver_contacts.is_empty() {
-                            let other = crate::utils::select_other(
-                                (inter.collider1, inter.collider2),
-                                *collider_handle,
-                            );
-                            if let Some(other_body) = colliders[other].parent {
-                                stack.push(other_body.handle);
-                            }
-                            break;
-                        }
-                    }
-                }
-            }
-        }
-
         // Now iterate on all active kinematic bodies and push all the bodies
         // touching them to the stack so they can be woken up.
         for h in self.active_kinematic_set.iter() {
@@ -235,9 +1037,12 @@ impl IslandManager {
         while let Some(handle) = self.stack.pop() {
             let rb = bodies.index_mut_internal(handle);
 
-            if rb.ids.active_set_timestamp == self.active_set_timestamp || !rb.is_dynamic() {
+            if rb.ids.active_set_timestamp == self.active_set_timestamp
+                || !rb.is_dynamic()
+                || !rb.is_enabled()
+            {
                 // We already visited this body and its neighbors.
-                // Also, we don't propagate awake state through fixed bodies.
+                // Also, we don't propagate awake state through fixed or disabled bodies.
                 continue;
             }
 
@@ -247,6 +1052,7 @@ impl IslandManager {
                 {
                     // We are starting a new island.
                     self.active_islands.push(self.active_dynamic_set.len());
+                    self.last_island_split_count += 1;
                 }
 
                 island_marker = self.stack.len();
@@ -254,7 +1060,11 @@ impl IslandManager {
 
             // Transmit the active state to all the rigid-bodies with colliders
             // in contact or joined with this collider.
-            push_contacting_bodies(&rb.colliders, colliders, narrow_phase, &mut self.stack);
+            if push_contacting_bodies(&rb.colliders, colliders, narrow_phase, &mut self.stack) {
+                rb.steps_since_contact = 0;
+            } else {
+                rb.steps_since_contact = rb.steps_since_contact.saturating_add(1);
+            }
 
             for inter in impulse_joints.attached_joints(handle) {
                 let other = crate::utils::select_other((inter.0, inter.1), handle);
@@ -265,7 +1075,19 @@ impl IslandManager {
                 self.stack.push(other);
             }
 
+            let was_sleeping = rb.activation.sleeping;
+
+            if was_sleeping {
+                self.last_woken = Some(handle);
+            }
+
+            // Bodies reached by the previous traversal were part of the previous active set.
+            if prev_timestamp == 0 || rb.ids.active_set_timestamp != prev_timestamp {
+                self.num_new_active_bodies += 1;
+            }
+
             rb.activation.wake_up(false);
+            rb.update_asleep_steps(timestep_count);
             rb.ids.active_island_id = self.active_islands.len() - 1;
             rb.ids.active_set_id = self.active_dynamic_set.len();
             rb.ids.active_set_offset =
@@ -273,9 +1095,47 @@ impl IslandManager {
             rb.ids.active_set_timestamp = self.active_set_timestamp;
 
             self.active_dynamic_set.push(handle);
+
+            #[cfg(feature = "trace-activation")]
+            if was_sleeping {
+                log::debug!(
+                    target: "rapier::activation",
+                    "{:?} woken up (cause: {})",
+                    handle,
+                    wake_cause(
+                        handle,
+                        bodies,
+                        colliders,
+                        narrow_phase,
+                        impulse_joints,
+                        multibody_joints,
+                        self.active_set_timestamp,
+                    )
+                );
+            }
         }
 
         self.active_islands.push(self.active_dynamic_set.len());
+
+        // Detect the bodies that changed island since the last update.
+        if self.track_island_changes {
+            for island in self.active_islands.windows(2) {
+                let island = &self.active_dynamic_set[island[0]..island[1]];
+                let key = island.iter().min_by_key(|h| h.into_raw_parts()).copied();
+
+                for handle in island {
+                    let rb = bodies.index_mut_internal(*handle);
+                    if rb.island_key != key {
+                        rb.island_key = key;
+                        rb.steps_since_island_change = 0;
+                    } else {
+                        rb.steps_since_island_change =
+                            rb.steps_since_island_change.saturating_add(1);
+                    }
+                }
+            }
+        }
+
         //        println!(
         //            "Extraction: {}, num islands: {}",
         //            instant::now() - t,
@@ -285,20 +1145,124 @@ impl IslandManager {
         // Actually put to sleep bodies which have not been detected as awake.
         for handle in &self.can_sleep {
             let rb = bodies.index_mut_internal(*handle);
-            if rb.activation.sleeping {
+            if rb.ids.active_set_timestamp != self.active_set_timestamp {
                 rb.vels = RigidBodyVelocity::zero();
                 rb.activation.sleep();
+                rb.update_asleep_steps(timestep_count);
+
+                #[cfg(feature = "trace-activation")]
+                log::debug!(
+                    target: "rapier::activation",
+                    "{:?} put to sleep (cause: below sleep thresholds)",
+                    handle
+                );
             }
         }
     }
 }
 
-fn update_energy(activation: &mut RigidBodyActivation, sq_linvel: Real, sq_angvel: Real, dt: Real) {
-    if sq_linvel < activation.linear_threshold * activation.linear_threshold.abs()
-        && sq_angvel < activation.angular_threshold * activation.angular_threshold.abs()
+// Read all the contacts and push objects touching touching this rigid-body.
+// Returns `true` if at least one contact manifold of this rigid-body has solver contacts.
+#[inline(always)]
+pub(crate) fn push_contacting_bodies(
+    rb_colliders: &RigidBodyColliders,
+    colliders: &ColliderSet,
+    narrow_phase: &NarrowPhase,
+    stack: &mut Vec<RigidBodyHandle>,
+) -> bool {
+    let mut has_solver_contacts = false;
+
+    for collider_handle in &rb_colliders.0 {
+        for inter in narrow_phase.contacts_with(*collider_handle) {
+            for manifold in &inter.manifolds {
+                if !manifold.data.solver_contacts.is_empty() {
+                    has_solver_contacts = true;
+                    let other = crate::utils::select_other(
+                        (inter.collider1, inter.collider2),
+                        *collider_handle,
+                    );
+                    if let Some(other_body) = colliders[other].parent {
+                        stack.push(other_body.handle);
+                    }
+                    break;
+                }
+            }
+        }
+    }
+
+    has_solver_contacts
+}
+
+// Guesses why the given rigid-body was reached by the active set traversal, given
+// the rigid-bodies already reached (i.e. stamped with `timestamp`).
+#[cfg(feature = "trace-activation")]
+fn wake_cause(
+    handle: RigidBodyHandle,
+    bodies: &RigidBodySet,
+    colliders: &ColliderSet,
+    narrow_phase: &NarrowPhase,
+    impulse_joints: &ImpulseJointSet,
+    multibody_joints: &MultibodyJointSet,
+    timestamp: u32,
+) -> &'static str {
+    let reached = |h: RigidBodyHandle| {
+        bodies
+            .get(h)
+            .map(|rb| rb.is_dynamic() && rb.ids.active_set_timestamp == timestamp)
+            == Some(true)
+    };
+
+    if impulse_joints
+        .attached_joints(handle)
+        .any(|(rb1, rb2, _, _)| reached(crate::utils::select_other((rb1, rb2), handle)))
+        || multibody_joints.attached_bodies(handle).any(reached)
+    {
+        return "joint";
+    }
+
+    let mut contacting = vec![];
+    push_contacting_bodies(
+        &bodies[handle].colliders,
+        colliders,
+        narrow_phase,
+        &mut contacting,
+    );
+
+    if contacting.iter().any(|h| reached(*h)) {
+        "contact"
+    } else if contacting
+        .iter()
+        .any(|h| bodies.get(*h).map(|rb| rb.is_kinematic()) == Some(true))
     {
-        activation.time_since_can_sleep += dt;
+        "kinematic"
     } else {
-        activation.time_since_can_sleep = 0.0;
+        "unknown"
     }
 }
+
+fn update_energy(activation: &mut RigidBodyActivation, sq_linvel: Real, sq_angvel: Real, dt: Real) {
+    activation.time_since_can_sleep =
+        updated_time_since_can_sleep(activation, sq_linvel, sq_angvel, dt);
+}
+
+fn updated_time_since_can_sleep(
+    activation: &RigidBodyActivation,
+    sq_linvel: Real,
+    sq_angvel: Real,
+    dt: Real,
+) -> Real {
+    if is_below_sleep_thresholds(activation, sq_linvel, sq_angvel) {
+        activation.time_since_can_sleep + dt
+    } else {
+        0.0
+    }
+}
+
+fn is_below_sleep_thresholds(
+    activation: &RigidBodyActivation,
+    sq_linvel: Real,
+    sq_angvel: Real,
+) -> bool {
+    sq_linvel < activation.linear_threshold * activation.linear_threshold.abs()
+        && sq_angvel < activation.angular_threshold * activation.angular_threshold.abs()
+}
@@ -265,6 +265,8 @@ impl ImpulseJointSet {
             let rb2 = &bodies[joint.body2];
 
             if (rb1.is_dynamic() || rb2.is_dynamic())
+                && rb1.is_enabled()
+                && rb2.is_enabled()
                 && (!rb1.is_dynamic() || !rb1.is_sleeping())
                 && (!rb2.is_dynamic() || !rb2.is_sleeping())
             {
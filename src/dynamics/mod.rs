@@ -3,7 +3,7 @@
 pub use self::ccd::CCDSolver;
 pub use self::coefficient_combine_rule::CoefficientCombineRule;
 pub use self::integration_parameters::IntegrationParameters;
-pub use self::island_manager::IslandManager;
+pub use self::island_manager::{AwakeBreakdown, IslandManager};
 pub(crate) use self::joint::JointGraphEdge;
 pub(crate) use self::joint::JointIndex;
 pub use self::joint::*;
@@ -15,7 +15,9 @@ pub(crate) use self::solver::ParallelIslandSolver;
 pub use parry::mass_properties::MassProperties;
 
 pub use self::rigid_body::{RigidBody, RigidBodyBuilder};
-pub use self::rigid_body_set::{BodyPair, RigidBodySet};
+pub use self::rigid_body_set::{
+    BodyPair, MassError, RigidBodySet, SlotStats, StagingBuffer, TypeCounts,
+};
 
 mod ccd;
 mod coefficient_combine_rule;
@@ -1,13 +1,14 @@
 use crate::dynamics::{
-    LockedAxes, MassProperties, RigidBodyActivation, RigidBodyAdditionalMassProps, RigidBodyCcd,
-    RigidBodyChanges, RigidBodyColliders, RigidBodyDamping, RigidBodyDominance, RigidBodyForces,
-    RigidBodyIds, RigidBodyMassProps, RigidBodyPosition, RigidBodyType, RigidBodyVelocity,
+    LeaseId, LockedAxes, MassProperties, RigidBodyActivation, RigidBodyAdditionalMassProps,
+    RigidBodyCcd, RigidBodyChanges, RigidBodyColliders, RigidBodyDamping, RigidBodyDominance,
+    RigidBodyForces, RigidBodyHandle, RigidBodyIds, RigidBodyMassProps, RigidBodyPosition,
+    RigidBodyType, RigidBodyVelocity,
 };
 use crate::geometry::{
     ColliderHandle, ColliderMassProps, ColliderParent, ColliderPosition, ColliderSet, ColliderShape,
 };
 use crate::math::{AngVector, Isometry, Point, Real, Rotation, Vector};
-use crate::utils::WCross;
+use crate::utils::{WAngularInertia, WCross};
 use num::Zero;
 
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
@@ -17,6 +18,8 @@ use num::Zero;
 #[derive(Debug, Clone)]
 pub struct RigidBody {
     pub(crate) pos: RigidBodyPosition,
+    // The position of the rigid-body at the beginning of the last timestep it was active.
+    pub(crate) prev_pos: Isometry<Real>,
     pub(crate) mprops: RigidBodyMassProps,
     // NOTE: we need this so that the CCD can use the actual velocities obtained
     //       by the velocity solver with bias. If we switch to interpolation, we
@@ -35,6 +38,43 @@ pub struct RigidBody {
     pub(crate) body_type: RigidBodyType,
     /// The dominance group this rigid-body is part of.
     pub(crate) dominance: RigidBodyDominance,
+    /// Whether or not the colliders attached to this rigid-body can generate collision events.
+    pub(crate) contact_events_enabled: bool,
+    /// The body type and velocities this rigid-body had before being paused.
+    pub(crate) paused_state: Option<(RigidBodyType, RigidBodyVelocity)>,
+    /// The world-space axis the local `y` axis of this rigid-body is driven toward, and the
+    /// stiffness of the restoring torque, if any.
+    pub(crate) upright_stabilization: Option<(Vector<Real>, Real)>,
+    /// The velocities this rigid-body is reset to at the end of the current timestep, if it is coasting.
+    pub(crate) coasting_vels: Option<RigidBodyVelocity>,
+    /// The linear and angular axes along which the velocity of this rigid-body is kept, if any.
+    pub(crate) velocity_axis_mask: Option<([bool; 3], [bool; 3])>,
+    /// The pose this rigid-body is reset to at the end of each timestep, if any.
+    pub(crate) held_pose: Option<Isometry<Real>>,
+    /// The timestep count of its rigid-body set when this rigid-body was inserted.
+    pub(crate) inserted_at_step: u32,
+    /// Whether or not the age of this rigid-body increases while it is sleeping.
+    pub(crate) ages_while_sleeping: bool,
+    /// The number of timesteps this rigid-body spent sleeping, excluded from its age, until
+    /// it was last woken up.
+    pub(crate) asleep_steps: u32,
+    /// The timestep count of its rigid-body set when this rigid-body fell asleep, if it is
+    /// sleeping and doesn't age while sleeping.
+    pub(crate) asleep_since_step: Option<u32>,
+    /// The number of active set updates since this rigid-body last had a solver contact.
+    pub(crate) steps_since_contact: u32,
+    /// The smallest handle of the island this rigid-body was part of during the last active set update.
+    pub(crate) island_key: Option<RigidBodyHandle>,
+    /// The number of active set updates since this rigid-body last changed island.
+    pub(crate) steps_since_island_change: u32,
+    /// Whether or not this rigid-body is simulated.
+    pub(crate) enabled: bool,
+    /// The leases currently keeping this rigid-body awake.
+    pub(crate) awake_leases: Vec<LeaseId>,
+    /// User-defined layer bits of this rigid-body, used by [`crate::dynamics::RigidBodySet::iter_layer`].
+    ///
+    /// This is unrelated to the collision groups of its colliders and doesn't affect the simulation.
+    pub layer: u32,
     /// User-defined data associated to this rigid-body.
     pub user_data: u128,
 }
@@ -49,6 +89,7 @@ impl RigidBody {
     fn new() -> Self {
         Self {
             pos: RigidBodyPosition::default(),
+            prev_pos: Isometry::identity(),
             mprops: RigidBodyMassProps::default(),
             integrated_vels: RigidBodyVelocity::default(),
             vels: RigidBodyVelocity::default(),
@@ -61,11 +102,32 @@ impl RigidBody {
             changes: RigidBodyChanges::all(),
             body_type: RigidBodyType::Dynamic,
             dominance: RigidBodyDominance::default(),
+            contact_events_enabled: true,
+            paused_state: None,
+            upright_stabilization: None,
+            coasting_vels: None,
+            velocity_axis_mask: None,
+            held_pose: None,
+            inserted_at_step: 0,
+            ages_while_sleeping: true,
+            asleep_steps: 0,
+            asleep_since_step: None,
+            steps_since_contact: 0,
+            island_key: None,
+            steps_since_island_change: 0,
+            enabled: true,
+            awake_leases: Vec::new(),
+            layer: 0,
             user_data: 0,
         }
     }
 
-    pub(crate) fn reset_internal_references(&mut self) {
+    pub(crate) fn reset_internal_references(&mut self, timestep_count: u32) {
+        self.inserted_at_step = timestep_count;
+        self.asleep_steps = 0;
+        self.asleep_since_step = None;
+        self.update_asleep_steps(timestep_count);
+        self.awake_leases = Vec::new();
         self.colliders.0 = Vec::new();
         self.ids = Default::default();
     }
@@ -122,6 +184,236 @@ impl RigidBody {
         }
     }
 
+    /// Pauses or resumes the simulation of this rigid-body.
+    ///
+    /// A paused rigid-body behaves like a fixed rigid-body: it keeps its current position,
+    /// isn’t affected by forces, and other rigid-bodies can rest on it and fall asleep. While
+    /// paused, [`Self::body_type`] returns [`RigidBodyType::Fixed`]. Resuming the rigid-body
+    /// restores the body type and the velocities it had when it was paused, and wakes it up.
+    pub fn set_paused(&mut self, paused: bool) {
+        if paused && self.paused_state.is_none() {
+            self.paused_state = Some((self.body_type, self.vels));
+            self.set_body_type(RigidBodyType::Fixed);
+        } else if !paused {
+            if let Some((body_type, vels)) = self.paused_state.take() {
+                self.set_body_type(body_type);
+                self.vels = vels;
+                self.wake_up(true);
+            }
+        }
+    }
+
+    /// Enables or disables the upright stabilization of this rigid-body.
+    ///
+    /// If set to `Some((axis, stiffness))`, a restoring torque drives the local `y` axis of this
+    /// rigid-body toward the world-space direction `axis` at each timestep. This torque is
+    /// proportional to `stiffness` and to the tilt between both axes, and is damped by the
+    /// angular velocity that changes the tilt, so rotations around the `y` axis are left
+    /// untouched. It is scaled by the angular inertia of the rigid-body along the tilt axis:
+    /// for small tilts, the rigid-body behaves like a critically damped oscillator with an
+    /// angular frequency of `stiffness.sqrt()`, whatever its mass properties. The rigid-body is
+    /// otherwise simulated normally. Setting `None` disables it.
+    pub fn set_upright_stabilization(&mut self, stabilization: Option<(Vector<Real>, Real)>) {
+        self.upright_stabilization = stabilization.map(|(axis, stiffness)| {
+            (
+                axis.try_normalize(1.0e-5).unwrap_or_else(Vector::y),
+                stiffness,
+            )
+        });
+    }
+
+    /// The target axis and stiffness of the upright stabilization of this rigid-body, if any.
+    pub fn upright_stabilization(&self) -> Option<(Vector<Real>, Real)> {
+        self.upright_stabilization
+    }
+
+    // The torque applied by the upright stabilization of this rigid-body.
+    pub(crate) fn upright_stabilization_torque(&self) -> AngVector<Real> {
+        let (axis, stiffness) = match self.upright_stabilization {
+            Some(stabilization) => stabilization,
+            None => return na::zero(),
+        };
+
+        let up = self.pos.position * Vector::y();
+        #[cfg(feature = "dim2")]
+        let tilt_vel = self.vels.angvel;
+        #[cfg(feature = "dim3")]
+        let tilt_vel = self.vels.angvel - up * up.dot(&self.vels.angvel);
+
+        let accel = up.gcross(axis) * stiffness - tilt_vel * (2.0 * stiffness.sqrt());
+        let inv_inertia = self.mprops.effective_world_inv_inertia_sqrt.squared();
+
+        #[cfg(feature = "dim2")]
+        let inertia = crate::utils::inv(inv_inertia);
+        #[cfg(feature = "dim3")]
+        let inertia = match accel.try_normalize(1.0e-8) {
+            Some(dir) => crate::utils::inv(dir.dot(&inv_inertia.transform_vector(dir))),
+            None => return na::zero(),
+        };
+
+        accel * inertia
+    }
+
+    /// Enables or disables the coasting of this rigid-body.
+    ///
+    /// A coasting rigid-body keeps its velocities exactly: it isn't affected by gravity, forces,
+    /// or damping, and its velocities are restored at the end of each timestep. Contacts and
+    /// joints are still solved so its motion during a timestep doesn't penetrate other
+    /// colliders, but they don't change its velocities. Enabling coasting wakes it up.
+    pub fn set_coasting(&mut self, on: bool) {
+        if on {
+            self.coasting_vels = Some(self.vels);
+            self.wake_up(true);
+        } else {
+            self.coasting_vels = None;
+        }
+    }
+
+    /// Is this rigid-body coasting?
+    ///
+    /// See [`Self::set_coasting`] for details.
+    pub fn is_coasting(&self) -> bool {
+        self.coasting_vels.is_some()
+    }
+
+    /// Sets the world-space axes along which the velocity of this rigid-body is allowed.
+    ///
+    /// The linear (resp. angular) velocity component along the `i`-th world-space axis is set
+    /// to zero whenever `linear_mask[i]` (resp. `angular_mask[i]`) is `false`. Unlike
+    /// [`LockedAxes`], this doesn't change the mass properties seen by the constraints solver:
+    /// masked components are discarded once the constraints are solved at each timestep, so
+    /// velocities resulting from contacts are discarded too. In 2D, the third linear component
+    /// and the first two angular components are ignored.
+    pub fn set_velocity_axis_mask(&mut self, linear_mask: [bool; 3], angular_mask: [bool; 3]) {
+        self.velocity_axis_mask = Some((linear_mask, angular_mask));
+    }
+
+    /// Removes the velocity axis mask set with [`Self::set_velocity_axis_mask`].
+    pub fn clear_velocity_axis_mask(&mut self) {
+        self.velocity_axis_mask = None;
+    }
+
+    /// The linear and angular velocity axis masks of this rigid-body, if any.
+    pub fn velocity_axis_mask(&self) -> Option<([bool; 3], [bool; 3])> {
+        self.velocity_axis_mask
+    }
+
+    // The given velocities with the components masked by the velocity axis mask set to zero.
+    pub(crate) fn masked_velocity(&self, mut vels: RigidBodyVelocity) -> RigidBodyVelocity {
+        if let Some((linear_mask, angular_mask)) = self.velocity_axis_mask {
+            for (vel, allowed) in vels.linvel.iter_mut().zip(linear_mask) {
+                if !allowed {
+                    *vel = 0.0;
+                }
+            }
+
+            #[cfg(feature = "dim2")]
+            if !angular_mask[2] {
+                vels.angvel = 0.0;
+            }
+
+            #[cfg(feature = "dim3")]
+            for (vel, allowed) in vels.angvel.iter_mut().zip(angular_mask) {
+                if !allowed {
+                    *vel = 0.0;
+                }
+            }
+        }
+
+        vels
+    }
+
+    /// Holds this rigid-body at the given pose until [`Self::release_hold`] is called.
+    ///
+    /// Unlike a kinematic rigid-body, a held rigid-body remains dynamic: it is integrated and
+    /// solved normally, so the rigid-bodies it touches are pushed realistically. However, its
+    /// position is reset to `pose` and its velocities are set to zero at the end of every
+    /// timestep. This wakes the rigid-body up.
+    pub fn hold_at(&mut self, pose: Isometry<Real>) {
+        self.held_pose = Some(pose);
+        self.wake_up(true);
+    }
+
+    /// Stops holding this rigid-body at the pose given to [`Self::hold_at`].
+    pub fn release_hold(&mut self) {
+        self.held_pose = None;
+    }
+
+    /// The pose this rigid-body is held at, if any.
+    pub fn held_pose(&self) -> Option<&Isometry<Real>> {
+        self.held_pose.as_ref()
+    }
+
+    /// The number of timesteps executed since this rigid-body was inserted into its set, given
+    /// the current timestep count of that set.
+    pub(crate) fn age_steps(&self, timestep_count: u32) -> u32 {
+        let asleep_steps = self
+            .asleep_since_step
+            .map(|since| {
+                self.asleep_steps
+                    .wrapping_add(timestep_count.wrapping_sub(since))
+            })
+            .unwrap_or(self.asleep_steps);
+        timestep_count
+            .wrapping_sub(self.inserted_at_step)
+            .wrapping_sub(asleep_steps)
+    }
+
+    /// Starts or stops counting the timesteps this rigid-body spends sleeping if it fell asleep
+    /// or was woken up, given the current timestep count of its set.
+    ///
+    /// Only the rigid-bodies which don't age while sleeping are tracked.
+    pub(crate) fn update_asleep_steps(&mut self, timestep_count: u32) {
+        let asleep = self.activation.sleeping && !self.ages_while_sleeping;
+
+        match self.asleep_since_step {
+            None if asleep => self.asleep_since_step = Some(timestep_count),
+            Some(since) if !asleep => {
+                self.asleep_steps = self
+                    .asleep_steps
+                    .wrapping_add(timestep_count.wrapping_sub(since));
+                self.asleep_since_step = None;
+            }
+            _ => {}
+        }
+    }
+
+    /// Sets whether or not the timesteps during which this rigid-body is sleeping are counted
+    /// by [`crate::dynamics::RigidBodySet::age_steps`] (default: `true`).
+    pub fn set_ages_while_sleeping(&mut self, enabled: bool) {
+        self.ages_while_sleeping = enabled;
+    }
+
+    /// The number of timesteps since this rigid-body last had a contact with at least one
+    /// solver contact.
+    ///
+    /// This is zero if this rigid-body touched something during the last timestep. It is only
+    /// updated while this rigid-body is dynamic and awake.
+    pub fn steps_since_contact(&self) -> u32 {
+        self.steps_since_contact
+    }
+
+    /// The number of timesteps since this rigid-body last changed island.
+    ///
+    /// An island is identified by the smallest handle of the rigid-bodies it contains, so this
+    /// is reset to zero whenever this rigid-body joins or leaves a group of interacting
+    /// rigid-bodies. It is only updated while this rigid-body is dynamic and awake, and if
+    /// the tracking of island changes is enabled with
+    /// [`crate::dynamics::IslandManager::set_track_island_changes`].
+    pub fn steps_since_island_change(&self) -> u32 {
+        self.steps_since_island_change
+    }
+
+    /// Is this rigid-body kept awake by at least one lease?
+    pub fn has_awake_leases(&self) -> bool {
+        !self.awake_leases.is_empty()
+    }
+
+    /// Is the simulation of this rigid-body paused?
+    pub fn is_paused(&self) -> bool {
+        self.paused_state.is_some()
+    }
+
     /// The mass-properties of this rigid-body.
     #[inline]
     pub fn mass_properties(&self) -> &MassProperties {
@@ -330,6 +622,20 @@ impl RigidBody {
         self.ccd.ccd_enabled
     }
 
+    /// Enables or disable the collision events of the colliders attached to this rigid-body.
+    ///
+    /// When disabled, the colliders attached to this rigid-body don’t generate any collision
+    /// event, even if their `ActiveEvents` contain `ActiveEvents::COLLISION_EVENTS`. Events may
+    /// still be generated if the other collider involved has collision events enabled.
+    pub fn enable_contact_events(&mut self, enabled: bool) {
+        self.contact_events_enabled = enabled;
+    }
+
+    /// Can the colliders attached to this rigid-body generate collision events?
+    pub fn is_contact_events_enabled(&self) -> bool {
+        self.contact_events_enabled
+    }
+
     // This is different from `is_ccd_enabled`. This checks that CCD
     // is active for this rigid-body, i.e., if it was seen to move fast
     // enough to justify a CCD run.
@@ -541,6 +847,10 @@ impl RigidBody {
     /// If `strong` is `true` then it is assured that the rigid-body will
     /// remain awake during multiple subsequent timesteps.
     pub fn wake_up(&mut self, strong: bool) {
+        if !self.enabled {
+            return;
+        }
+
         if self.activation.sleeping {
             self.changes.insert(RigidBodyChanges::SLEEP);
         }
@@ -548,6 +858,32 @@ impl RigidBody {
         self.activation.wake_up(strong);
     }
 
+    /// Enables or disables this rigid-body.
+    ///
+    /// A disabled rigid-body is kept in its set, with its handle and colliders, but it is
+    /// excluded from the simulation: it is removed from the active sets, isn't moved, and its
+    /// colliders don't generate any contact or intersection. A disabled rigid-body is
+    /// sleeping and can't be woken up. Enabling a rigid-body wakes it up.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        if enabled != self.enabled {
+            self.enabled = enabled;
+            self.changes.insert(RigidBodyChanges::ENABLED_OR_DISABLED);
+
+            if enabled {
+                self.wake_up(true);
+            } else {
+                self.activation.sleep();
+            }
+        }
+    }
+
+    /// Is this rigid-body enabled?
+    ///
+    /// See [`Self::set_enabled`] for details.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
     /// Is this rigid body sleeping?
     pub fn is_sleeping(&self) -> bool {
         // TODO: should we:
@@ -650,6 +986,13 @@ impl RigidBody {
         &self.pos.position
     }
 
+    /// The world-space position of this rigid-body at the beginning of the last timestep
+    /// during which it was awake.
+    #[inline]
+    pub fn previous_position(&self) -> &Isometry<Real> {
+        &self.prev_pos
+    }
+
     /// The translational part of this rigid-body's position.
     #[inline]
     pub fn translation(&self) -> &Vector<Real> {
@@ -963,6 +1306,8 @@ pub struct RigidBodyBuilder {
     pub ccd_enabled: bool,
     /// The dominance group of the rigid-body to be built.
     pub dominance_group: i8,
+    /// The user-defined layer bits of the rigid-body to be built.
+    pub layer: u32,
     /// An arbitrary user-defined 128-bit integer associated to the rigid-bodies built by this builder.
     pub user_data: u128,
 }
@@ -984,6 +1329,7 @@ impl RigidBodyBuilder {
             sleeping: false,
             ccd_enabled: false,
             dominance_group: 0,
+            layer: 0,
             user_data: 0,
         }
     }
@@ -1054,6 +1400,14 @@ impl RigidBodyBuilder {
         self
     }
 
+    /// Sets the user-defined layer bits of the rigid-body to be built.
+    ///
+    /// See [`RigidBody::layer`] for details.
+    pub fn layer(mut self, layer: u32) -> Self {
+        self.layer = layer;
+        self
+    }
+
     /// An arbitrary user-defined 128-bit integer associated to the rigid-bodies built by this builder.
     pub fn user_data(mut self, data: u128) -> Self {
         self.user_data = data;
@@ -1235,9 +1589,11 @@ impl RigidBodyBuilder {
         let mut rb = RigidBody::new();
         rb.pos.next_position = self.position; // FIXME: compute the correct value?
         rb.pos.position = self.position;
+        rb.prev_pos = self.position;
         rb.vels.linvel = self.linvel;
         rb.vels.angvel = self.angvel;
         rb.body_type = self.body_type;
+        rb.layer = self.layer;
         rb.user_data = self.user_data;
 
         if self.additional_mass_properties
@@ -46,6 +46,13 @@ impl IndexedData for RigidBodyHandle {
     }
 }
 
+/// The identifier of a lease keeping a rigid-body awake.
+///
+/// See [`RigidBodySet::acquire_awake_lease`](crate::dynamics::RigidBodySet::acquire_awake_lease).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct LeaseId(pub(crate) u32);
+
 /// The type of a body, governing the way it is affected by external forces.
 #[deprecated(note = "renamed as RigidBodyType")]
 pub type BodyStatus = RigidBodyType;
@@ -112,6 +119,8 @@ bitflags::bitflags! {
         const DOMINANCE   = 1 << 5;
         /// Flag indicating that the local mass-properties of this rigid-body must be recomputed.
         const LOCAL_MASS_PROPERTIES = 1 << 6;
+        /// Flag indicating that the rigid-body was enabled or disabled.
+        const ENABLED_OR_DISABLED = 1 << 7;
     }
 }
 
@@ -839,6 +848,7 @@ pub struct RigidBodyIds {
     pub(crate) active_set_id: usize,
     pub(crate) active_set_offset: usize,
     pub(crate) active_set_timestamp: u32,
+    pub(crate) insertion_timestamp: Option<u32>,
 }
 
 impl Default for RigidBodyIds {
@@ -848,6 +858,7 @@ impl Default for RigidBodyIds {
             active_set_id: 0,
             active_set_offset: 0,
             active_set_timestamp: 0,
+            insertion_timestamp: None,
         }
     }
 }
@@ -1,10 +1,28 @@
 use crate::data::Arena;
 use crate::dynamics::{
-    ImpulseJointSet, IslandManager, MultibodyJointSet, RigidBody, RigidBodyChanges, RigidBodyHandle,
+    GenericJoint, ImpulseJointHandle, ImpulseJointSet, IslandManager, LeaseId, MultibodyJointSet,
+    RigidBody, RigidBodyChanges, RigidBodyHandle, RigidBodyType, RigidBodyVelocity,
 };
-use crate::geometry::ColliderSet;
+use crate::geometry::{ColliderHandle, ColliderSet, ContactPair, NarrowPhase, AABB};
+use crate::math::{AngVector, AngularInertia, Isometry, Point, Real, Vector, DIM};
+use crate::pipeline::QueryPipeline;
+use crate::utils::WAngularInertia;
+use bit_vec::BitVec;
+use parry::bounding_volume::BoundingVolume;
+use parry::query::PointQuery;
+use parry::utils::hashmap::HashMap;
+use std::collections::HashSet;
 use std::ops::{Index, IndexMut};
 
+// The number of coordinates used to encode a rotation in a pose frame.
+#[cfg(feature = "dim2")]
+const POSE_FRAME_ROTATION_COORDS: usize = 2;
+#[cfg(feature = "dim3")]
+const POSE_FRAME_ROTATION_COORDS: usize = 4;
+// The size, in bytes, of the encoding of one rigid-body in a pose frame.
+const POSE_FRAME_RECORD_SIZE: usize =
+    8 + (DIM + POSE_FRAME_ROTATION_COORDS) * std::mem::size_of::<Real>();
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 /// A pair of rigid body handles.
@@ -22,6 +40,79 @@ impl BodyPair {
     }
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+/// An invalid mass property detected on a dynamic rigid-body.
+pub enum MassError {
+    /// The inverse mass is NaN or infinite.
+    NonFiniteMass,
+    /// The inverse mass is negative.
+    NegativeMass,
+    /// One of the principal inverse angular inertia components is NaN or infinite.
+    NonFiniteInertia,
+    /// One of the principal inverse angular inertia components is negative.
+    NegativeInertia,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+/// The number of rigid-bodies of each type contained by a rigid-body set.
+pub struct TypeCounts {
+    /// The number of dynamic rigid-bodies, including the sleeping ones.
+    pub dynamic: usize,
+    /// The number of kinematic rigid-bodies, either position-based or velocity-based.
+    pub kinematic: usize,
+    /// The number of fixed rigid-bodies.
+    pub fixed: usize,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+/// Statistics about the storage slots of a rigid-body set.
+pub struct SlotStats {
+    /// The number of slots containing a rigid-body.
+    pub used: usize,
+    /// The number of slots that don't contain any rigid-body and are available for insertions.
+    pub free: usize,
+    /// The number of slots reserved by [`RigidBodySet::reserve_handles`] and not filled yet.
+    pub reserved: usize,
+    /// The total number of slots, i.e., `used + free + reserved`.
+    pub capacity: usize,
+}
+
+#[derive(Clone, Default)]
+/// A buffer of rigid-bodies waiting to be inserted into a rigid-body set.
+///
+/// Staging buffers don’t borrow the rigid-body set so they can be filled independently, e.g.,
+/// by multiple threads. All their rigid-bodies are then inserted at once with
+/// [`RigidBodySet::commit_staging`].
+pub struct StagingBuffer {
+    bodies: Vec<RigidBody>,
+}
+
+impl StagingBuffer {
+    /// Creates a new empty staging buffer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a rigid-body to this buffer and returns its provisional id.
+    ///
+    /// The provisional id is the index of the final handle of this rigid-body in the vector
+    /// returned by [`RigidBodySet::commit_staging`].
+    pub fn push(&mut self, rb: impl Into<RigidBody>) -> usize {
+        self.bodies.push(rb.into());
+        self.bodies.len() - 1
+    }
+
+    /// The number of rigid-bodies in this buffer.
+    pub fn len(&self) -> usize {
+        self.bodies.len()
+    }
+
+    /// `true` if there are no rigid-bodies in this buffer.
+    pub fn is_empty(&self) -> bool {
+        self.bodies.is_empty()
+    }
+}
+
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 #[derive(Clone, Default)]
 /// A set of rigid bodies that can be handled by a physics pipeline.
@@ -32,6 +123,15 @@ pub struct RigidBodySet {
     // Could we avoid this?
     pub(crate) bodies: Arena<RigidBody>,
     pub(crate) modified_bodies: Vec<RigidBodyHandle>,
+    // Groups of rigid-bodies forced to sleep once their countdown of timesteps reaches zero.
+    settling_groups: Vec<(u32, Vec<RigidBodyHandle>)>,
+    next_lease_id: u32,
+    // Handles of the storage slots reserved by `reserve_handles` and not filled yet.
+    reserved_handles: Vec<RigidBodyHandle>,
+    // Minimum island size overriding the one from the integration parameters.
+    min_island_size_override: Option<usize>,
+    // The number of timesteps executed with this set, used to compute the age of its rigid-bodies.
+    pub(crate) timestep_count: u32,
 }
 
 impl RigidBodySet {
@@ -40,7 +140,49 @@ impl RigidBodySet {
         RigidBodySet {
             bodies: Arena::new(),
             modified_bodies: Vec::new(),
+            settling_groups: Vec::new(),
+            next_lease_id: 0,
+            reserved_handles: Vec::new(),
+            min_island_size_override: None,
+            timestep_count: 0,
+        }
+    }
+
+    /// Create a new empty set of rigid bodies able to hold `capacity` rigid-bodies without
+    /// reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        RigidBodySet {
+            bodies: Arena::with_capacity(capacity),
+            modified_bodies: Vec::with_capacity(capacity),
+            ..Self::new()
+        }
+    }
+
+    /// Reserves capacity for at least `additional` more rigid-bodies to be inserted.
+    pub fn reserve(&mut self, additional: usize) {
+        let missing_slots = additional.saturating_sub(self.free_slot_count());
+        if missing_slots > 0 {
+            self.bodies.reserve(missing_slots);
         }
+        self.modified_bodies.reserve(additional);
+    }
+
+    /// Overrides the minimum island size from the integration parameters for the simulation
+    /// of this set.
+    ///
+    /// A temporary override set with [`IslandManager::set_min_island_size_for_steps`] still
+    /// takes precedence over this one.
+    ///
+    /// # Panics
+    /// Panics if `size` is zero.
+    pub fn set_min_island_size(&mut self, size: usize) {
+        assert!(size > 0, "The minimum island size must be at least 1.");
+        self.min_island_size_override = Some(size);
+    }
+
+    /// The minimum island size set with [`Self::set_min_island_size`], if any.
+    pub fn min_island_size(&self) -> Option<usize> {
+        self.min_island_size_override
     }
 
     pub(crate) fn take_modified(&mut self) -> Vec<RigidBodyHandle> {
@@ -57,17 +199,177 @@ impl RigidBodySet {
         self.bodies.is_empty()
     }
 
+    /// The total number of storage slots of this set, used or not.
+    ///
+    /// This is the number of rigid-bodies this set can contain before its storage grows.
+    pub fn capacity(&self) -> usize {
+        self.bodies.capacity()
+    }
+
+    /// The number of rigid-bodies of each type on this set.
+    ///
+    /// This scans all the rigid-bodies, so sleeping dynamic rigid-bodies are counted too.
+    pub fn counts_by_type(&self) -> TypeCounts {
+        let mut counts = TypeCounts::default();
+
+        for (_, rb) in self.iter() {
+            match rb.body_type() {
+                RigidBodyType::Dynamic => counts.dynamic += 1,
+                RigidBodyType::KinematicPositionBased | RigidBodyType::KinematicVelocityBased => {
+                    counts.kinematic += 1
+                }
+                RigidBodyType::Fixed => counts.fixed += 1,
+            }
+        }
+
+        counts
+    }
+
+    /// The sum of the kinetic energies of all the dynamic rigid-bodies of this set.
+    ///
+    /// Sleeping rigid-bodies have a zero velocity, so they only contribute to the sum if they
+    /// were given a velocity since they fell asleep. They are skipped if `include_sleeping` is
+    /// `false`.
+    pub fn total_kinetic_energy(&self, include_sleeping: bool) -> Real {
+        self.iter()
+            .filter(|(_, rb)| rb.is_dynamic() && (include_sleeping || !rb.is_sleeping()))
+            .map(|(_, rb)| rb.kinetic_energy())
+            .sum()
+    }
+
+    /// The number of rigid-bodies with each possible number of attached colliders.
+    ///
+    /// The element at index `i` of the returned vector is the number of rigid-bodies with
+    /// exactly `i` colliders attached. The vector is empty if this set is empty.
+    pub fn collider_count_histogram(&self) -> Vec<usize> {
+        let mut histogram = vec![];
+
+        for (_, rb) in self.iter() {
+            let num_colliders = rb.colliders().len();
+            if histogram.len() <= num_colliders {
+                histogram.resize(num_colliders + 1, 0);
+            }
+            histogram[num_colliders] += 1;
+        }
+
+        histogram
+    }
+
+    /// The number of free slots in the storage of this set.
+    ///
+    /// Free slots are left by removed rigid-bodies, or allocated in advance, and are reused by
+    /// subsequent insertions. Slots reserved with [`Self::reserve_handles`] are not free since
+    /// only [`Self::fill_reserved`] can use them. This is an `O(1)` operation.
+    pub fn free_slot_count(&self) -> usize {
+        self.bodies.capacity() - self.bodies.len() - self.reserved_handles.len()
+    }
+
+    /// The number of free slots divided by the total number of slots in the storage of this set.
+    ///
+    /// Returns zero if no slot was allocated yet. This is an `O(1)` operation.
+    pub fn fragmentation_ratio(&self) -> Real {
+        self.free_slot_count() as Real / self.bodies.capacity().max(1) as Real
+    }
+
+    /// Statistics about the storage slots of this set.
+    ///
+    /// See [`Self::free_slot_count`] for details about free slots. This is an `O(1)` operation.
+    pub fn slot_stats(&self) -> SlotStats {
+        SlotStats {
+            used: self.bodies.len(),
+            free: self.free_slot_count(),
+            reserved: self.reserved_handles.len(),
+            capacity: self.bodies.capacity(),
+        }
+    }
+
+    /// A bitset indicating which rigid-bodies of this set are sleeping.
+    ///
+    /// The bit at index `i` is set if the storage slot `i` (the first element of
+    /// [`RigidBodyHandle::into_raw_parts`]) contains a sleeping rigid-body. The bitset has one
+    /// bit per slot, including free slots. It can be applied back to this set with
+    /// [`IslandManager::apply_sleeping_bitset`].
+    pub fn sleeping_bitset(&self) -> BitVec {
+        let mut bits = BitVec::from_elem(self.bodies.capacity(), false);
+
+        for (handle, rb) in self.bodies.iter() {
+            if rb.is_sleeping() {
+                bits.set(handle.into_raw_parts().0 as usize, true);
+            }
+        }
+
+        bits
+    }
+
     /// Is the given body handle valid?
     pub fn contains(&self, handle: RigidBodyHandle) -> bool {
         self.bodies.contains(handle.0)
     }
 
+    /// The index of the rigid-body with the given handle in the active set of the
+    /// [`IslandManager`] it is simulated with.
+    ///
+    /// If the rigid-body is awake then `active_dynamic_bodies()[id] == handle` (or
+    /// `active_kinematic_bodies()[id] == handle` for kinematic rigid-bodies). If it is sleeping
+    /// or fixed, the returned index is stale and must not be relied upon. Returns `None` if the
+    /// handle is invalid.
+    pub fn active_set_id(&self, handle: RigidBodyHandle) -> Option<usize> {
+        self.get(handle).map(|rb| rb.ids.active_set_id)
+    }
+
+    /// The arena slot index and the generation of every rigid-body of this set.
+    ///
+    /// The generation of a slot is bumped whenever it is reused after a removal, so a
+    /// slot with a generation that keeps climbing indicates rigid-bodies being repeatedly
+    /// removed and re-inserted.
+    pub fn slot_reuse_counts(&self) -> Vec<(usize, u64)> {
+        self.bodies
+            .iter()
+            .map(|(h, _)| {
+                let (index, generation) = h.into_raw_parts();
+                (index as usize, generation as u64)
+            })
+            .collect()
+    }
+
+    /// Checks the mass properties of every dynamic rigid-body of this set.
+    ///
+    /// Returns the handles of the dynamic rigid-bodies with a NaN, infinite, or negative inverse
+    /// mass or principal inverse angular inertia, together with the detected error. A zero inverse
+    /// mass or inertia is valid: it describes an infinite mass or inertia. No allocation happens if
+    /// all the mass properties are valid.
+    pub fn validate_mass_properties(&self) -> Vec<(RigidBodyHandle, MassError)> {
+        let mut errors = vec![];
+
+        for (handle, rb) in self.iter().filter(|(_, rb)| rb.is_dynamic()) {
+            let mprops = rb.mass_properties();
+            #[cfg(feature = "dim2")]
+            let inv_inertia = [mprops.inv_principal_inertia_sqrt];
+            #[cfg(feature = "dim3")]
+            let inv_inertia = mprops.inv_principal_inertia_sqrt;
+
+            if !mprops.inv_mass.is_finite() {
+                errors.push((handle, MassError::NonFiniteMass));
+            } else if mprops.inv_mass < 0.0 {
+                errors.push((handle, MassError::NegativeMass));
+            }
+
+            if inv_inertia.iter().any(|i| !i.is_finite()) {
+                errors.push((handle, MassError::NonFiniteInertia));
+            } else if inv_inertia.iter().any(|i| *i < 0.0) {
+                errors.push((handle, MassError::NegativeInertia));
+            }
+        }
+
+        errors
+    }
+
     /// Insert a rigid body into this set and retrieve its handle.
     pub fn insert(&mut self, rb: impl Into<RigidBody>) -> RigidBodyHandle {
         let mut rb = rb.into();
         // Make sure the internal links are reset, they may not be
         // if this rigid-body was obtained by cloning another one.
-        rb.reset_internal_references();
+        rb.reset_internal_references(self.timestep_count);
         rb.changes.set(RigidBodyChanges::all(), true);
 
         let handle = RigidBodyHandle(self.bodies.insert(rb));
@@ -75,6 +377,251 @@ impl RigidBodySet {
         handle
     }
 
+    /// Reserves `n` storage slots and returns the handles the rigid-bodies inserted into them
+    /// with [`Self::fill_reserved`] will have.
+    ///
+    /// The returned handles are invalid until they are filled. Reserved slots are not used
+    /// by other insertions, which makes slot assignment deterministic for pools of rigid-bodies.
+    pub fn reserve_handles(&mut self, n: usize) -> Vec<RigidBodyHandle> {
+        let handles: Vec<_> = (0..n)
+            .map(|_| RigidBodyHandle(self.bodies.reserve_slot()))
+            .collect();
+        self.reserved_handles.extend_from_slice(&handles);
+        handles
+    }
+
+    /// Inserts a rigid-body into a slot reserved with [`Self::reserve_handles`].
+    ///
+    /// Once filled, `handle` identifies the inserted rigid-body. Returns `false`, and doesn't
+    /// insert anything, if `handle` isn't a reserved handle that wasn't filled yet.
+    pub fn fill_reserved(&mut self, handle: RigidBodyHandle, rb: impl Into<RigidBody>) -> bool {
+        let i = match self.reserved_handles.iter().position(|h| *h == handle) {
+            Some(i) => i,
+            None => return false,
+        };
+        self.reserved_handles.swap_remove(i);
+
+        let mut rb = rb.into();
+        // Make sure the internal links are reset, they may not be
+        // if this rigid-body was obtained by cloning another one.
+        rb.reset_internal_references(self.timestep_count);
+        rb.changes.set(RigidBodyChanges::all(), true);
+
+        self.bodies.insert_reserved(handle.0, rb);
+        self.modified_bodies.push(handle);
+        true
+    }
+
+    /// Inserts all the given rigid-bodies into this set.
+    ///
+    /// This is equivalent to calling [`Self::insert`] on each rigid-body, but reserves the
+    /// storage for all of them at once. Returns the handles of the inserted rigid-bodies, in
+    /// the same order as `bodies`.
+    pub fn insert_batch(
+        &mut self,
+        bodies: impl IntoIterator<Item = RigidBody>,
+    ) -> Vec<RigidBodyHandle> {
+        let bodies = bodies.into_iter();
+        let (num_bodies, _) = bodies.size_hint();
+        self.reserve(num_bodies);
+
+        let handles: Vec<_> = bodies
+            .map(|mut rb| {
+                // Make sure the internal links are reset, they may not be
+                // if this rigid-body was obtained by cloning another one.
+                rb.reset_internal_references(self.timestep_count);
+                rb.changes.set(RigidBodyChanges::all(), true);
+                RigidBodyHandle(self.bodies.insert(rb))
+            })
+            .collect();
+        self.modified_bodies.extend_from_slice(&handles);
+        handles
+    }
+
+    /// Inserts a copy of the rigid-body `template` at the given pose.
+    ///
+    /// The new rigid-body has the same type, mass properties, damping, locked axes, and flags
+    /// as `template`, but it is awake, has no velocity, no user force or torque, and no
+    /// collider attached. Its mass properties, including the contributions of the colliders
+    /// of `template`, are copied instead of being recomputed at the next timestep. They are
+    /// only recomputed, from its own colliders and additional mass properties, once colliders
+    /// are attached to it or its mass properties are modified. Returns `None`, and doesn't
+    /// insert anything, if `template` is invalid.
+    pub fn insert_like(
+        &mut self,
+        template: RigidBodyHandle,
+        pose: Isometry<Real>,
+    ) -> Option<RigidBodyHandle> {
+        let mut rb = self.get(template)?.clone();
+        rb.set_position(pose, false);
+        rb.prev_pos = pose;
+        rb.vels = RigidBodyVelocity::zero();
+        rb.reset_forces(false);
+        rb.reset_torques(false);
+        rb.activation.wake_up(true);
+        rb.mprops.update_world_mass_properties(&pose);
+
+        let handle = self.insert(rb);
+        // Keep the copied mass properties: recomputing them from the (still empty) list of
+        // colliders would discard the contributions of the template's colliders.
+        self.bodies[handle.0]
+            .changes
+            .remove(RigidBodyChanges::LOCAL_MASS_PROPERTIES | RigidBodyChanges::COLLIDERS);
+        Some(handle)
+    }
+
+    /// Appends to `out` a compact binary encoding of the handle and position of every
+    /// rigid-body of this set.
+    ///
+    /// Each rigid-body is encoded as the two raw parts of its handle (as little-endian `u32`),
+    /// followed by its translation and rotation coordinates (as little-endian `Real`). The
+    /// rotation is encoded as the real and imaginary parts of a unit complex number in 2D,
+    /// and as the `i, j, k, w` coordinates of a unit quaternion in 3D.
+    /// The frame can be applied back with [`Self::read_pose_frame`].
+    pub fn write_pose_frame(&self, out: &mut Vec<u8>) {
+        out.reserve(self.len() * POSE_FRAME_RECORD_SIZE);
+
+        for (handle, rb) in self.iter() {
+            let (index, generation) = handle.into_raw_parts();
+            out.extend_from_slice(&index.to_le_bytes());
+            out.extend_from_slice(&generation.to_le_bytes());
+
+            let pos = rb.position();
+            #[cfg(feature = "dim2")]
+            let rotation = [pos.rotation.re, pos.rotation.im];
+            #[cfg(feature = "dim3")]
+            let rotation = pos.rotation.coords;
+
+            for coord in pos.translation.vector.iter().chain(rotation.iter()) {
+                out.extend_from_slice(&coord.to_le_bytes());
+            }
+        }
+    }
+
+    /// Applies the rigid-body positions encoded in a frame generated by [`Self::write_pose_frame`].
+    ///
+    /// Handles that don’t identify any rigid-body of this set are ignored, and so are trailing
+    /// bytes that don’t form a complete record. The rigid-bodies are not woken up.
+    pub fn read_pose_frame(&mut self, data: &[u8]) {
+        for record in data.chunks_exact(POSE_FRAME_RECORD_SIZE) {
+            let index = u32::from_le_bytes(record[0..4].try_into().unwrap());
+            let generation = u32::from_le_bytes(record[4..8].try_into().unwrap());
+            let mut coords = record[8..]
+                .chunks_exact(std::mem::size_of::<Real>())
+                .map(|bytes| Real::from_le_bytes(bytes.try_into().unwrap()));
+
+            let translation = Vector::from_fn(|_, _| coords.next().unwrap());
+            #[cfg(feature = "dim2")]
+            let rotation = na::UnitComplex::new_unchecked(na::Complex::new(
+                coords.next().unwrap(),
+                coords.next().unwrap(),
+            ));
+            #[cfg(feature = "dim3")]
+            let rotation = na::UnitQuaternion::new_unchecked(na::Quaternion::from_vector(
+                na::Vector4::from_fn(|_, _| coords.next().unwrap()),
+            ));
+
+            let handle = RigidBodyHandle::from_raw_parts(index, generation);
+            if let Some(rb) = self.get_mut_internal_with_modification_tracking(handle) {
+                rb.set_position(Isometry::from_parts(translation.into(), rotation), false);
+            }
+        }
+    }
+
+    /// Creates an empty staging buffer for rigid-bodies to be inserted later into this set.
+    pub fn begin_staging() -> StagingBuffer {
+        StagingBuffer::new()
+    }
+
+    /// Inserts all the rigid-bodies from the given staging buffer into this set.
+    ///
+    /// The returned vector maps the provisional ids given by [`StagingBuffer::push`] to the
+    /// handles of the inserted rigid-bodies.
+    pub fn commit_staging(&mut self, buffer: StagingBuffer) -> Vec<RigidBodyHandle> {
+        self.insert_batch(buffer.bodies)
+    }
+
+    /// Inserts a group of rigid-bodies that are simulated for `settle_steps` timesteps before
+    /// being forced to sleep all together with [`IslandManager::sleep_many`].
+    ///
+    /// This is useful for letting structures like stacks settle their contacts at load time
+    /// without having them stay awake because of small jitters. All the rigid-bodies are
+    /// inserted awake. Returns the handles of the inserted rigid-bodies, in the same order.
+    pub fn insert_settling_group(
+        &mut self,
+        bodies: Vec<RigidBody>,
+        settle_steps: u32,
+    ) -> Vec<RigidBodyHandle> {
+        let handles: Vec<_> = bodies
+            .into_iter()
+            .map(|mut rb| {
+                rb.activation.wake_up(true);
+                self.insert(rb)
+            })
+            .collect();
+        self.settling_groups.push((settle_steps, handles.clone()));
+        handles
+    }
+
+    /// The number of timesteps executed since the rigid-body with the given handle was inserted.
+    ///
+    /// Timesteps during which the rigid-body was sleeping are not counted if
+    /// [`RigidBody::set_ages_while_sleeping`] was called with `false`. Returns `None` if the
+    /// handle is invalid.
+    pub fn age_steps(&self, handle: RigidBodyHandle) -> Option<u32> {
+        self.get(handle).map(|rb| rb.age_steps(self.timestep_count))
+    }
+
+    /// Advances the countdown of every settling group by one timestep, and puts to sleep
+    /// the rigid-bodies of the groups that finished settling.
+    pub(crate) fn update_settling_groups(&mut self, islands: &mut IslandManager) {
+        let mut settled = vec![];
+        self.settling_groups
+            .retain_mut(|(remaining_steps, handles)| {
+                *remaining_steps = remaining_steps.saturating_sub(1);
+
+                if *remaining_steps > 0 {
+                    return true;
+                }
+
+                settled.append(handles);
+                false
+            });
+
+        islands.sleep_many(self, &settled);
+    }
+
+    /// Removes all the impulse joints and multibody joints attached to the rigid-body with the
+    /// given handle, without removing the rigid-body itself nor its colliders.
+    ///
+    /// The rigid-body, as well as the rigid-bodies it was attached to, are woken up.
+    pub fn detach_joints(
+        &mut self,
+        handle: RigidBodyHandle,
+        impulse_joints: &mut ImpulseJointSet,
+        multibody_joints: &mut MultibodyJointSet,
+    ) {
+        let mut to_wake_up: Vec<_> = impulse_joints
+            .attached_joints(handle)
+            .map(|(rb1, rb2, _, _)| crate::utils::select_other((rb1, rb2), handle))
+            .chain(
+                multibody_joints
+                    .attached_joints(handle)
+                    .map(|(rb1, rb2, _)| crate::utils::select_other((rb1, rb2), handle)),
+            )
+            .collect();
+        to_wake_up.push(handle);
+
+        impulse_joints.remove_joints_attached_to_rigid_body(handle);
+        multibody_joints.remove_joints_attached_to_rigid_body(handle);
+
+        for handle in to_wake_up {
+            if let Some(rb) = self.get_mut_internal_with_modification_tracking(handle) {
+                rb.wake_up(true);
+            }
+        }
+    }
+
     /// Removes a rigid-body, and all its attached colliders and impulse_joints, from these sets.
     pub fn remove(
         &mut self,
@@ -91,6 +638,32 @@ impl RigidBodySet {
          */
         islands.rigid_body_removed(handle, &rb.ids, self);
 
+        self.remove_attachments(
+            handle,
+            &rb,
+            islands,
+            colliders,
+            impulse_joints,
+            multibody_joints,
+            remove_attached_colliders,
+        );
+
+        Some(rb)
+    }
+
+    // Removes or detaches the colliders, and removes the joints, attached to the rigid-body
+    // `rb` that was just removed from this set.
+    #[allow(clippy::too_many_arguments)]
+    fn remove_attachments(
+        &mut self,
+        handle: RigidBodyHandle,
+        rb: &RigidBody,
+        islands: &mut IslandManager,
+        colliders: &mut ColliderSet,
+        impulse_joints: &mut ImpulseJointSet,
+        multibody_joints: &mut MultibodyJointSet,
+        remove_attached_colliders: bool,
+    ) {
         /*
          * Remove colliders attached to this rigid-body.
          */
@@ -111,8 +684,228 @@ impl RigidBodySet {
          */
         impulse_joints.remove_joints_attached_to_rigid_body(handle);
         multibody_joints.remove_joints_attached_to_rigid_body(handle);
+    }
 
-        Some(rb)
+    /// Removes all the rigid-bodies from this set, and the joints attached to them.
+    ///
+    /// The colliders attached to the removed rigid-bodies are either removed or detached,
+    /// depending on `remove_attached_colliders`, like with [`Self::remove`]. The active sets
+    /// and islands of `islands` are then reset, and its active set timestamp is set back to
+    /// zero. The storage allocated by this set and by `islands` is kept for reuse, and handles
+    /// of the removed rigid-bodies remain invalid. Slots reserved with [`Self::reserve_handles`]
+    /// are released, so their handles can no longer be filled.
+    pub fn clear(
+        &mut self,
+        islands: &mut IslandManager,
+        colliders: &mut ColliderSet,
+        impulse_joints: &mut ImpulseJointSet,
+        multibody_joints: &mut MultibodyJointSet,
+        remove_attached_colliders: bool,
+    ) {
+        let handles: Vec<_> = self.iter().map(|(h, _)| h).collect();
+
+        for handle in handles {
+            self.remove(
+                handle,
+                islands,
+                colliders,
+                impulse_joints,
+                multibody_joints,
+                remove_attached_colliders,
+            );
+        }
+
+        for handle in self.reserved_handles.drain(..) {
+            self.bodies.release_reserved(handle.0);
+        }
+
+        self.modified_bodies.clear();
+        self.settling_groups.clear();
+        islands.clear_active_sets();
+    }
+
+    /// Removes several rigid-bodies, and all their attached colliders and joints, from these sets.
+    ///
+    /// This is equivalent to calling [`Self::remove`] on each handle with
+    /// `remove_attached_colliders` set to `true`, but the active sets of `islands` are updated
+    /// in a single pass instead of once per removed rigid-body. The returned vector contains the
+    /// removed rigid-bodies in the same order as `handles`, with `None` for handles that were
+    /// invalid or appeared earlier in `handles`.
+    pub fn remove_batch(
+        &mut self,
+        handles: &[RigidBodyHandle],
+        islands: &mut IslandManager,
+        colliders: &mut ColliderSet,
+        impulse_joints: &mut ImpulseJointSet,
+        multibody_joints: &mut MultibodyJointSet,
+    ) -> Vec<Option<RigidBody>> {
+        let removed: Vec<_> = handles
+            .iter()
+            .map(|handle| self.bodies.remove(handle.0))
+            .collect();
+        let removed_ids: Vec<_> = handles
+            .iter()
+            .zip(&removed)
+            .filter_map(|(handle, rb)| Some((*handle, rb.as_ref()?.ids)))
+            .collect();
+
+        islands.rigid_bodies_removed(&removed_ids, self);
+
+        for (handle, rb) in handles.iter().zip(&removed) {
+            if let Some(rb) = rb {
+                self.remove_attachments(
+                    *handle,
+                    rb,
+                    islands,
+                    colliders,
+                    impulse_joints,
+                    multibody_joints,
+                    true,
+                );
+            }
+        }
+
+        removed
+    }
+
+    /// Moves a rigid-body, together with its attached colliders, from these sets to the
+    /// `dst_bodies` and `dst_colliders` sets.
+    ///
+    /// This is [`RigidBodySet::migrate_bodies_to`] with a single rigid-body: all the joints
+    /// attached to it are removed. Returns the handle of the rigid-body in `dst_bodies`, or
+    /// `None` if `handle` is invalid.
+    #[allow(clippy::too_many_arguments)]
+    pub fn migrate_body_to(
+        &mut self,
+        handle: RigidBodyHandle,
+        islands: &mut IslandManager,
+        colliders: &mut ColliderSet,
+        impulse_joints: &mut ImpulseJointSet,
+        multibody_joints: &mut MultibodyJointSet,
+        dst_bodies: &mut RigidBodySet,
+        dst_colliders: &mut ColliderSet,
+        dst_impulse_joints: &mut ImpulseJointSet,
+        dst_multibody_joints: &mut MultibodyJointSet,
+    ) -> Option<RigidBodyHandle> {
+        self.migrate_bodies_to(
+            &[handle],
+            islands,
+            colliders,
+            impulse_joints,
+            multibody_joints,
+            dst_bodies,
+            dst_colliders,
+            dst_impulse_joints,
+            dst_multibody_joints,
+        )[0]
+    }
+
+    /// Moves several rigid-bodies, together with their attached colliders, from these sets to
+    /// the `dst_bodies` and `dst_colliders` sets.
+    ///
+    /// Each rigid-body keeps its position, velocities, age, and its other properties, and each
+    /// collider keeps its position relative to its rigid-body. Awake leases are released
+    /// since lease ids are specific to a rigid-body set. The joints between two migrated
+    /// rigid-bodies are re-created in `dst_impulse_joints` and `dst_multibody_joints`; the
+    /// joints with only one migrated end are removed.
+    ///
+    /// Returns, for each handle, the handle of the rigid-body in `dst_bodies`, or `None` if
+    /// that handle is invalid.
+    #[allow(clippy::too_many_arguments)]
+    pub fn migrate_bodies_to(
+        &mut self,
+        handles: &[RigidBodyHandle],
+        islands: &mut IslandManager,
+        colliders: &mut ColliderSet,
+        impulse_joints: &mut ImpulseJointSet,
+        multibody_joints: &mut MultibodyJointSet,
+        dst_bodies: &mut RigidBodySet,
+        dst_colliders: &mut ColliderSet,
+        dst_impulse_joints: &mut ImpulseJointSet,
+        dst_multibody_joints: &mut MultibodyJointSet,
+    ) -> Vec<Option<RigidBodyHandle>> {
+        let migrated: HashSet<_> = handles
+            .iter()
+            .copied()
+            .filter(|handle| self.contains(*handle))
+            .collect();
+
+        // Collect the joints to re-create before their removal. Each impulse joint is collected
+        // from its first body, and each multibody joint from its child body.
+        let mut kept_impulse_joints = vec![];
+        let mut kept_multibody_joints = vec![];
+
+        for handle in &migrated {
+            for (rb1, rb2, _, joint) in impulse_joints.attached_joints(*handle) {
+                if rb1 == *handle && migrated.contains(&rb2) {
+                    kept_impulse_joints.push((rb1, rb2, joint.data));
+                }
+            }
+
+            for (rb1, rb2, joint_handle) in multibody_joints.attached_joints(*handle) {
+                if rb2 == *handle && migrated.contains(&rb1) {
+                    if let Some((multibody, link_id)) = multibody_joints.get(joint_handle) {
+                        let data = multibody.link(link_id).unwrap().joint.data;
+                        kept_multibody_joints.push((rb1, rb2, data));
+                    }
+                }
+            }
+        }
+
+        let mut dst_handles = HashMap::default();
+        let result = handles
+            .iter()
+            .map(|handle| {
+                let co_handles = self.get(*handle)?.colliders().to_vec();
+                let attached_colliders: Vec<_> = co_handles
+                    .into_iter()
+                    .filter_map(|co_handle| colliders.remove(co_handle, islands, self, false))
+                    .collect();
+                let rb = self.remove(
+                    *handle,
+                    islands,
+                    colliders,
+                    impulse_joints,
+                    multibody_joints,
+                    false,
+                )?;
+
+                // Shift the timestep counts recorded by the rigid-body to the timestep count
+                // of `dst_bodies` so it keeps its age.
+                let shift = dst_bodies.timestep_count.wrapping_sub(self.timestep_count);
+                let (inserted_at_step, asleep_steps, asleep_since_step) =
+                    (rb.inserted_at_step, rb.asleep_steps, rb.asleep_since_step);
+                let dst_handle = dst_bodies.insert(rb);
+                let dst_rb = dst_bodies.index_mut_internal(dst_handle);
+                dst_rb.inserted_at_step = inserted_at_step.wrapping_add(shift);
+                dst_rb.asleep_steps = asleep_steps;
+                dst_rb.asleep_since_step = asleep_since_step.map(|since| since.wrapping_add(shift));
+
+                for collider in attached_colliders {
+                    dst_colliders.insert_with_parent(collider, dst_handle, dst_bodies);
+                }
+
+                let _ = dst_handles.insert(*handle, dst_handle);
+                Some(dst_handle)
+            })
+            .collect();
+
+        // Sort the joints by handle so they are re-created in a deterministic order.
+        let sort_key = |(rb1, rb2, _): &(RigidBodyHandle, RigidBodyHandle, GenericJoint)| {
+            (rb1.into_raw_parts(), rb2.into_raw_parts())
+        };
+        kept_impulse_joints.sort_by_key(sort_key);
+        kept_multibody_joints.sort_by_key(sort_key);
+
+        for (rb1, rb2, data) in kept_impulse_joints {
+            let _ = dst_impulse_joints.insert(dst_handles[&rb1], dst_handles[&rb2], data, true);
+        }
+
+        for (rb1, rb2, data) in kept_multibody_joints {
+            let _ = dst_multibody_joints.insert(dst_handles[&rb1], dst_handles[&rb2], data, true);
+        }
+
+        result
     }
 
     /// Gets the rigid-body with the given handle without a known generation.
@@ -171,6 +964,22 @@ impl RigidBodySet {
         Some(result)
     }
 
+    /// Gets mutable references to the `N` rigid-bodies with the given handles.
+    ///
+    /// Returns `None` if any of the handles is invalid, or if the same handle is given twice.
+    /// Like [`Self::get_mut`], this marks all these rigid-bodies as modified.
+    #[cfg(not(feature = "dev-remove-slow-accessors"))]
+    pub fn get_many_mut<const N: usize>(
+        &mut self,
+        handles: [RigidBodyHandle; N],
+    ) -> Option<[&mut RigidBody; N]> {
+        let mut result = self.bodies.get_many_mut(handles.map(|h| h.0))?;
+        for (handle, rb) in handles.iter().zip(result.iter_mut()) {
+            Self::mark_as_modified(*handle, rb, &mut self.modified_bodies);
+        }
+        Some(result)
+    }
+
     pub(crate) fn get_mut_internal(&mut self, handle: RigidBodyHandle) -> Option<&mut RigidBody> {
         self.bodies.get_mut(handle.0)
     }
@@ -190,13 +999,611 @@ impl RigidBodySet {
         Some(result)
     }
 
-    /// Iterates through all the rigid-bodies on this set.
-    pub fn iter(&self) -> impl Iterator<Item = (RigidBodyHandle, &RigidBody)> {
-        self.bodies.iter().map(|(h, b)| (RigidBodyHandle(h), b))
+    /// The inverse mass of the rigid-body with the given handle along each world-space axis.
+    ///
+    /// Components along locked translation axes are zero. Returns `None` if the handle is
+    /// invalid.
+    pub fn effective_inv_mass(&self, handle: RigidBodyHandle) -> Option<Vector<Real>> {
+        self.get(handle).map(|rb| rb.mprops.effective_inv_mass)
     }
 
-    /// Iterates mutably through all the rigid-bodies on this set.
-    #[cfg(not(feature = "dev-remove-slow-accessors"))]
+    /// The world-space inverse angular inertia tensor of the rigid-body with the given handle.
+    ///
+    /// This takes into account the current orientation of the rigid-body, as well as its locked
+    /// rotation axes. Returns `None` if the handle is invalid.
+    pub fn effective_world_inv_inertia(
+        &self,
+        handle: RigidBodyHandle,
+    ) -> Option<AngularInertia<Real>> {
+        self.get(handle)
+            .map(|rb| rb.mprops.effective_world_inv_inertia_sqrt.squared())
+    }
+
+    /// Sets the world-space position of the rigid-body with the given handle.
+    ///
+    /// Only the position is modified: the velocities are left untouched. The attached colliders
+    /// are moved accordingly at the beginning of the next timestep. If `wake_up` is `true` and
+    /// the rigid-body is dynamic, it is woken up.
+    pub fn set_position(&mut self, handle: RigidBodyHandle, pos: Isometry<Real>, wake_up: bool) {
+        if let Some(rb) = self.get_mut_internal_with_modification_tracking(handle) {
+            rb.set_position(pos, wake_up);
+        }
+    }
+
+    /// Pauses or resumes the simulation of the rigid-body with the given handle.
+    ///
+    /// See [`RigidBody::set_paused`] for details.
+    pub fn set_paused(&mut self, handle: RigidBodyHandle, paused: bool) {
+        if let Some(rb) = self.get_mut_internal_with_modification_tracking(handle) {
+            rb.set_paused(paused);
+        }
+    }
+
+    /// Enables or disables the rigid-body with the given handle.
+    ///
+    /// See [`RigidBody::set_enabled`] for details.
+    pub fn set_enabled(&mut self, handle: RigidBodyHandle, enabled: bool) {
+        if let Some(rb) = self.get_mut_internal_with_modification_tracking(handle) {
+            rb.set_enabled(enabled);
+        }
+    }
+
+    /// Sets the linear velocity of the rigid-body with the given handle.
+    ///
+    /// If `wake_up` is `true` and the rigid-body is sleeping, it is woken up and added back
+    /// to the active set at the beginning of the next timestep. See [`RigidBody::set_linvel`]
+    /// for details.
+    pub fn set_linvel(&mut self, handle: RigidBodyHandle, linvel: Vector<Real>, wake_up: bool) {
+        if let Some(rb) = self.get_mut_internal_with_modification_tracking(handle) {
+            rb.set_linvel(linvel, wake_up);
+        }
+    }
+
+    /// Sets the angular velocity of the rigid-body with the given handle.
+    ///
+    /// If `wake_up` is `true` and the rigid-body is sleeping, it is woken up and added back
+    /// to the active set at the beginning of the next timestep. See [`RigidBody::set_angvel`]
+    /// for details.
+    pub fn set_angvel(&mut self, handle: RigidBodyHandle, angvel: AngVector<Real>, wake_up: bool) {
+        if let Some(rb) = self.get_mut_internal_with_modification_tracking(handle) {
+            rb.set_angvel(angvel, wake_up);
+        }
+    }
+
+    /// Enables or disables the coasting of the rigid-body with the given handle.
+    ///
+    /// See [`RigidBody::set_coasting`] for details.
+    pub fn set_coasting(&mut self, handle: RigidBodyHandle, on: bool) {
+        if let Some(rb) = self.get_mut_internal_with_modification_tracking(handle) {
+            rb.set_coasting(on);
+        }
+    }
+
+    /// Sets the world-space axes along which the velocity of the rigid-body with the given
+    /// handle is allowed.
+    ///
+    /// See [`RigidBody::set_velocity_axis_mask`] for details.
+    pub fn set_velocity_axis_mask(
+        &mut self,
+        handle: RigidBodyHandle,
+        linear_mask: [bool; 3],
+        angular_mask: [bool; 3],
+    ) {
+        if let Some(rb) = self.get_mut_internal_with_modification_tracking(handle) {
+            rb.set_velocity_axis_mask(linear_mask, angular_mask);
+        }
+    }
+
+    /// The number of timesteps since the rigid-body with the given handle last touched anything.
+    ///
+    /// See [`RigidBody::steps_since_contact`] for details. Returns `None` if the handle is invalid.
+    pub fn airborne_steps(&self, handle: RigidBodyHandle) -> Option<u32> {
+        self.get(handle).map(|rb| rb.steps_since_contact)
+    }
+
+    /// The number of timesteps since the rigid-body with the given handle last changed island.
+    ///
+    /// See [`RigidBody::steps_since_island_change`] for details. Returns `None` if the handle is invalid.
+    pub fn steps_since_island_change(&self, handle: RigidBodyHandle) -> Option<u32> {
+        self.get(handle).map(|rb| rb.steps_since_island_change)
+    }
+
+    /// Enables or disables the upright stabilization of the rigid-body with the given handle.
+    ///
+    /// See [`RigidBody::set_upright_stabilization`] for details.
+    pub fn set_upright_stabilization(
+        &mut self,
+        handle: RigidBodyHandle,
+        stabilization: Option<(Vector<Real>, Real)>,
+    ) {
+        if let Some(rb) = self.get_mut_internal_with_modification_tracking(handle) {
+            rb.set_upright_stabilization(stabilization);
+            rb.wake_up(true);
+        }
+    }
+
+    /// Holds the rigid-body with the given handle at the given pose until
+    /// [`Self::release_hold`] is called.
+    ///
+    /// See [`RigidBody::hold_at`] for details.
+    pub fn hold_at(&mut self, handle: RigidBodyHandle, pose: Isometry<Real>) {
+        if let Some(rb) = self.get_mut_internal_with_modification_tracking(handle) {
+            rb.hold_at(pose);
+        }
+    }
+
+    /// Stops holding the rigid-body with the given handle at a fixed pose.
+    pub fn release_hold(&mut self, handle: RigidBodyHandle) {
+        if let Some(rb) = self.bodies.get_mut(handle.0) {
+            rb.release_hold();
+        }
+    }
+
+    /// Resets the sleep timer of the rigid-body with the given handle, without waking it up.
+    ///
+    /// An awake rigid-body will have to stay still for the full sleep delay again before
+    /// falling asleep. This has no effect on sleeping rigid-bodies.
+    pub fn touch_activation(&mut self, handle: RigidBodyHandle) {
+        if let Some(rb) = self.bodies.get_mut(handle.0) {
+            if !rb.activation.sleeping {
+                rb.activation.time_since_can_sleep = 0.0;
+            }
+        }
+    }
+
+    /// Acquires a lease keeping the rigid-body with the given handle awake.
+    ///
+    /// The rigid-body is woken up, and won’t fall asleep as long as at least one of its leases is
+    /// held. Independent systems can acquire their own leases on the same rigid-body without
+    /// interfering with each other. Returns `None` if the handle is invalid.
+    pub fn acquire_awake_lease(&mut self, handle: RigidBodyHandle) -> Option<LeaseId> {
+        let lease = LeaseId(self.next_lease_id);
+        let rb = self.get_mut_internal_with_modification_tracking(handle)?;
+        rb.awake_leases.push(lease);
+        rb.wake_up(true);
+        self.next_lease_id = self.next_lease_id.wrapping_add(1);
+        Some(lease)
+    }
+
+    /// Releases a lease acquired with [`Self::acquire_awake_lease`].
+    ///
+    /// The rigid-body is allowed to fall asleep again once all its leases are released.
+    /// Returns `false` if the handle is invalid or if the lease isn’t held by this rigid-body.
+    pub fn release_awake_lease(&mut self, handle: RigidBodyHandle, lease: LeaseId) -> bool {
+        if let Some(rb) = self.bodies.get_mut(handle.0) {
+            if let Some(i) = rb.awake_leases.iter().position(|l| *l == lease) {
+                rb.awake_leases.swap_remove(i);
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Enables or disables the collision events of the colliders attached to the rigid-body with
+    /// the given handle.
+    ///
+    /// See [`RigidBody::enable_contact_events`] for details.
+    pub fn set_contact_events_enabled(&mut self, handle: RigidBodyHandle, enabled: bool) {
+        if let Some(rb) = self.bodies.get_mut(handle.0) {
+            rb.enable_contact_events(enabled);
+        }
+    }
+
+    /// Translates the rigid-body with the given handle by `delta`, without modifying its
+    /// velocities.
+    ///
+    /// The attached colliders are moved accordingly at the beginning of the next timestep. If
+    /// `wake_up` is `true` and the rigid-body is dynamic, it is woken up.
+    pub fn translate(&mut self, handle: RigidBodyHandle, delta: Vector<Real>, wake_up: bool) {
+        if let Some(rb) = self.get_mut_internal_with_modification_tracking(handle) {
+            let mut pos = *rb.position();
+            pos.translation.vector += delta;
+            rb.set_position(pos, wake_up);
+        }
+    }
+
+    /// Exchanges the positions, and optionally the velocities, of two rigid-bodies.
+    ///
+    /// Both rigid-bodies are woken up. Velocities are only assigned to rigid-bodies able to have
+    /// one (i.e. not fixed and not kinematic position-based). Returns `false` if any of the
+    /// handles is invalid.
+    pub fn swap_poses(
+        &mut self,
+        handle1: RigidBodyHandle,
+        handle2: RigidBodyHandle,
+        include_velocity: bool,
+    ) -> bool {
+        if !self.contains(handle1) || !self.contains(handle2) {
+            return false;
+        }
+
+        if handle1 == handle2 {
+            return true;
+        }
+
+        if let (Some(rb1), Some(rb2)) = self.bodies.get2_mut(handle1.0, handle2.0) {
+            Self::mark_as_modified(handle1, rb1, &mut self.modified_bodies);
+            Self::mark_as_modified(handle2, rb2, &mut self.modified_bodies);
+
+            let (pos1, pos2) = (*rb1.position(), *rb2.position());
+            let (vels1, vels2) = (rb1.vels, rb2.vels);
+
+            rb1.set_position(pos2, true);
+            rb2.set_position(pos1, true);
+
+            if include_velocity {
+                rb1.set_linvel(vels2.linvel, true);
+                rb1.set_angvel(vels2.angvel, true);
+                rb2.set_linvel(vels1.linvel, true);
+                rb2.set_angvel(vels1.angvel, true);
+            }
+
+            rb1.wake_up(true);
+            rb2.wake_up(true);
+        }
+
+        true
+    }
+
+    /// The sum of the constant forces applied by the user to the rigid-body with the given handle.
+    ///
+    /// These are the forces added with [`RigidBody::add_force`] and
+    /// [`RigidBody::add_force_at_point`]. They are not cleared by timesteps, only by
+    /// [`RigidBody::reset_forces`]. Gravity is not included. Returns `None` if the handle
+    /// is invalid.
+    pub fn accumulated_force(&self, handle: RigidBodyHandle) -> Option<Vector<Real>> {
+        self.get(handle).map(|rb| rb.forces.user_force)
+    }
+
+    /// The sum of the constant torques applied by the user to the rigid-body with the given handle.
+    ///
+    /// These are the torques added with [`RigidBody::add_torque`], and the torques resulting
+    /// from [`RigidBody::add_force_at_point`]. They are not cleared by timesteps, only by
+    /// [`RigidBody::reset_torques`]. Returns `None` if the handle is invalid.
+    pub fn accumulated_torque(&self, handle: RigidBodyHandle) -> Option<AngVector<Real>> {
+        self.get(handle).map(|rb| rb.forces.user_torque)
+    }
+
+    /// The angular velocity of the rigid-body `b` relative to the rigid-body `a`, i.e.,
+    /// `b.angvel - a.angvel`.
+    ///
+    /// Both angular velocities are expressed in world-space. Returns `None` if any of the
+    /// handles is invalid.
+    pub fn relative_angular_velocity(
+        &self,
+        a: RigidBodyHandle,
+        b: RigidBodyHandle,
+    ) -> Option<AngVector<Real>> {
+        let rb_a = self.get(a)?;
+        let rb_b = self.get(b)?;
+        Some(rb_b.vels.angvel - rb_a.vels.angvel)
+    }
+
+    /// The angular velocity of the rigid-body `b` relative to the rigid-body `a`, projected
+    /// onto the world-space `axis` (e.g. the axis of a hinge joint).
+    ///
+    /// The `axis` is expected to be normalized. Returns `None` if any of the handles is invalid.
+    #[cfg(feature = "dim3")]
+    pub fn relative_angular_velocity_along(
+        &self,
+        a: RigidBodyHandle,
+        b: RigidBodyHandle,
+        axis: &Vector<Real>,
+    ) -> Option<Real> {
+        self.relative_angular_velocity(a, b)
+            .map(|angvel| angvel.dot(axis))
+    }
+
+    /// The position of the rigid-body `handle2` expressed in the local-space of the
+    /// rigid-body `handle1`.
+    ///
+    /// Returns `None` if any of the handles is invalid.
+    pub fn relative_transform(
+        &self,
+        handle1: RigidBodyHandle,
+        handle2: RigidBodyHandle,
+    ) -> Option<Isometry<Real>> {
+        self.get_pair_with_relative(handle1, handle2)
+            .map(|(_, _, pos12)| pos12)
+    }
+
+    /// Gets the two rigid-bodies with the given handles, together with the position of the
+    /// second one expressed in the local-space of the first one.
+    ///
+    /// Returns `None` if any of the handles is invalid.
+    pub fn get_pair_with_relative(
+        &self,
+        handle1: RigidBodyHandle,
+        handle2: RigidBodyHandle,
+    ) -> Option<(&RigidBody, &RigidBody, Isometry<Real>)> {
+        let rb1 = self.get(handle1)?;
+        let rb2 = self.get(handle2)?;
+        let pos12 = rb1.position().inv_mul(rb2.position());
+        Some((rb1, rb2, pos12))
+    }
+
+    /// The velocity of the center-of-mass of the rigid-body with the given handle.
+    ///
+    /// This is the linear velocity of the rigid-body. Returns `None` if the handle is invalid.
+    pub fn velocity_at_center_of_mass(&self, handle: RigidBodyHandle) -> Option<Vector<Real>> {
+        self.get(handle).map(|rb| *rb.linvel())
+    }
+
+    /// The velocity of the given world-space point attached to the rigid-body with the given
+    /// handle.
+    ///
+    /// Returns `None` if the handle is invalid.
+    pub fn velocity_at_point(
+        &self,
+        handle: RigidBodyHandle,
+        point: &Point<Real>,
+    ) -> Option<Vector<Real>> {
+        self.get(handle).map(|rb| rb.velocity_at_point(point))
+    }
+
+    /// Applies an angular impulse at the center-of-mass of the rigid-body with the given handle.
+    ///
+    /// This does nothing on non-dynamic bodies. If `wake_up` is `true` and the rigid-body was
+    /// sleeping, it is woken up and added back to the active set at the beginning of the next
+    /// timestep so the impulse is not lost.
+    pub fn apply_torque_impulse(
+        &mut self,
+        handle: RigidBodyHandle,
+        torque_impulse: AngVector<Real>,
+        wake_up: bool,
+    ) {
+        if let Some(rb) = self.get_mut_internal_with_modification_tracking(handle) {
+            rb.apply_torque_impulse(torque_impulse, wake_up);
+        }
+    }
+
+    /// Applies a radial impulse centered at `center` to all the dynamic rigid-bodies with a
+    /// collider whose AABB is closer than `radius` from `center`.
+    ///
+    /// The impulse applied to each rigid-body points from `center` toward its center of mass.
+    /// Its magnitude decreases linearly from `strength` to zero as the distance between
+    /// `center` and the closest collider AABB of the rigid-body increases from zero to
+    /// `radius`. The affected rigid-bodies are woken up. The colliders are found using
+    /// `query_pipeline`, which must be up-to-date with `colliders`. Rigid-bodies with a center
+    /// of mass located at `center` are left untouched since the direction of their impulse is
+    /// undefined.
+    pub fn apply_explosion(
+        &mut self,
+        center: Point<Real>,
+        radius: Real,
+        strength: Real,
+        colliders: &ColliderSet,
+        query_pipeline: &QueryPipeline,
+    ) {
+        let explosion_aabb = AABB::new(
+            center - Vector::repeat(radius),
+            center + Vector::repeat(radius),
+        );
+        let mut affected = vec![];
+
+        query_pipeline.colliders_with_aabb_intersecting_aabb(&explosion_aabb, |co_handle| {
+            if let Some(co) = colliders.get(*co_handle) {
+                let parent = co
+                    .parent()
+                    .filter(|h| self.get(*h).map(|rb| rb.is_dynamic()) == Some(true));
+                if let Some(parent) = parent {
+                    let dist = co.compute_aabb().distance_to_local_point(&center, true);
+                    if dist < radius {
+                        affected.push((parent, dist));
+                    }
+                }
+            }
+            true
+        });
+
+        // Keep the closest collider of each rigid-body.
+        affected.sort_by(|a, b| {
+            a.0.into_raw_parts()
+                .cmp(&b.0.into_raw_parts())
+                .then(a.1.total_cmp(&b.1))
+        });
+        affected.dedup_by_key(|(handle, _)| *handle);
+
+        for (handle, dist) in affected {
+            if let Some(rb) = self.get_mut_internal_with_modification_tracking(handle) {
+                if let Some(dir) = na::Unit::try_new(rb.mprops.world_com - center, Real::EPSILON) {
+                    rb.apply_impulse(*dir * strength * (1.0 - dist / radius), true);
+                }
+            }
+        }
+    }
+
+    /// Iterates through all the rigid-bodies modified since the last timestep.
+    ///
+    /// This includes the rigid-bodies inserted, or accessed mutably, since then. The
+    /// modification tracking is left untouched, so the next timestep still accounts for
+    /// these modifications.
+    pub fn iter_modified(&self) -> impl Iterator<Item = (RigidBodyHandle, &RigidBody)> {
+        self.modified_bodies
+            .iter()
+            .filter_map(move |h| Some((*h, self.get(*h)?)))
+    }
+
+    /// Clears the modification tracking of all the rigid-bodies modified since the last
+    /// timestep.
+    ///
+    /// After this call, [`Self::iter_modified`] yields nothing until a rigid-body is inserted
+    /// or accessed mutably again. This doesn't affect the colliders or the active sets.
+    ///
+    /// The modifications cleared this way are never seen by the physics pipeline: collider
+    /// positions aren't synchronized with a rigid-body moved manually, and newly inserted
+    /// rigid-bodies aren't added to the active sets. This is intended to be called right after
+    /// a full timestep, before any further modification.
+    pub fn clear_modified(&mut self) {
+        for handle in self.modified_bodies.drain(..) {
+            if let Some(rb) = self.bodies.get_mut(handle.0) {
+                rb.changes = RigidBodyChanges::empty();
+            }
+        }
+    }
+
+    /// Iterates through all the rigid-bodies on this set.
+    pub fn iter(&self) -> impl Iterator<Item = (RigidBodyHandle, &RigidBody)> {
+        self.bodies.iter().map(|(h, b)| (RigidBodyHandle(h), b))
+    }
+
+    /// Iterates through all the rigid-bodies on this set, in the reverse order of [`Self::iter`].
+    ///
+    /// Removed rigid-bodies leave free slots which are reused by subsequent insertions, so
+    /// this only yields the most recently inserted rigid-bodies first if no rigid-body was
+    /// removed. Use [`Self::age_steps`] or [`Self::bodies_inserted_since`] for an ordering
+    /// independent from slot reuse.
+    pub fn iter_rev(&self) -> impl Iterator<Item = (RigidBodyHandle, &RigidBody)> {
+        self.bodies
+            .iter()
+            .rev()
+            .map(|(h, b)| (RigidBodyHandle(h), b))
+    }
+
+    /// Iterates through all the rigid-bodies on this set, sorted by the raw parts
+    /// `(index, generation)` of their handles.
+    ///
+    /// This order only depends on the sequence of insertions and removals applied to this set,
+    /// so two sets that went through the same operations yield their rigid-bodies in the same
+    /// order. Since each index is occupied by at most one rigid-body, this is also the order
+    /// of [`Self::iter`], and no sorting or allocation is needed.
+    pub fn iter_sorted(&self) -> impl Iterator<Item = (RigidBodyHandle, &RigidBody)> {
+        self.iter()
+    }
+
+    /// Clears `out` and fills it with the handle and position of every rigid-body on this set.
+    ///
+    /// This lets the caller reuse the same buffer across timesteps to avoid allocations.
+    pub fn collect_positions_into(&self, out: &mut Vec<(RigidBodyHandle, Isometry<Real>)>) {
+        out.clear();
+        out.extend(self.iter().map(|(h, rb)| (h, *rb.position())));
+    }
+
+    /// Clears `out` and fills it with the handle and position of every awake dynamic or
+    /// kinematic rigid-body.
+    ///
+    /// See [`Self::collect_positions_into`] for details.
+    pub fn collect_positions_into_active_only(
+        &self,
+        islands: &IslandManager,
+        out: &mut Vec<(RigidBodyHandle, Isometry<Real>)>,
+    ) {
+        out.clear();
+        out.extend(
+            islands
+                .active_dynamic_bodies()
+                .iter()
+                .chain(islands.active_kinematic_bodies())
+                .filter_map(|h| Some((*h, *self.get(*h)?.position()))),
+        );
+    }
+
+    /// Iterates through all the rigid-bodies on this set with at least one [`RigidBody::layer`]
+    /// bit in common with `mask`.
+    pub fn iter_layer(&self, mask: u32) -> impl Iterator<Item = (RigidBodyHandle, &RigidBody)> {
+        self.iter().filter(move |(_, rb)| rb.layer & mask != 0)
+    }
+
+    /// Iterates through all the rigid-bodies on this set with CCD enabled.
+    ///
+    /// See [`RigidBody::enable_ccd`] for details.
+    pub fn iter_ccd_enabled(&self) -> impl Iterator<Item = (RigidBodyHandle, &RigidBody)> {
+        self.iter().filter(|(_, rb)| rb.is_ccd_enabled())
+    }
+
+    /// Did the rigid-body with the given handle move during the last timestep it was awake?
+    ///
+    /// Returns `true` if the distance between its previous and current translations is greater
+    /// than `lin_eps`, or if the angle between its previous and current orientations is
+    /// greater than `ang_eps`. Returns `None` if the handle is invalid.
+    pub fn moved_since_last_step(
+        &self,
+        handle: RigidBodyHandle,
+        lin_eps: Real,
+        ang_eps: Real,
+    ) -> Option<bool> {
+        let rb = self.get(handle)?;
+        let prev_pos = rb.previous_position();
+        let pos = rb.position();
+        let lin_dist = (pos.translation.vector - prev_pos.translation.vector).norm();
+        let ang_dist = prev_pos.rotation.angle_to(&pos.rotation);
+        Some(lin_dist > lin_eps || ang_dist > ang_eps)
+    }
+
+    /// The AABB swept by the colliders attached to the rigid-body with the given handle while
+    /// moving from its previous position to its current position.
+    ///
+    /// This is the union of the AABBs of the attached colliders at the positions returned by
+    /// [`RigidBody::previous_position`] and [`RigidBody::position`]. Returns `None` if the
+    /// handle is invalid or if the rigid-body has no collider attached.
+    pub fn swept_aabb(&self, handle: RigidBodyHandle, colliders: &ColliderSet) -> Option<AABB> {
+        let rb = self.get(handle)?;
+        rb.colliders()
+            .iter()
+            .filter_map(|co_handle| colliders.get(*co_handle))
+            .map(|co| {
+                let prev_pos = co
+                    .position_wrt_parent()
+                    .map(|pos_wrt_parent| rb.previous_position() * pos_wrt_parent)
+                    .unwrap_or(*rb.previous_position());
+                co.compute_aabb()
+                    .merged(&co.shape().compute_aabb(&prev_pos))
+            })
+            .reduce(|aabb1, aabb2| aabb1.merged(&aabb2))
+    }
+
+    /// Iterates through all the rigid-bodies on this set, sorted by increasing dot product
+    /// between their translation and `axis`.
+    ///
+    /// This sorts all the rigid-bodies on each call so it costs `O(n log n)` time and allocates
+    /// a vector of `n` elements, where `n` is the number of rigid-bodies on this set.
+    pub fn iter_sorted_by_axis(
+        &self,
+        axis: Vector<Real>,
+    ) -> impl Iterator<Item = (RigidBodyHandle, &RigidBody)> {
+        let mut sorted: Vec<_> = self
+            .iter()
+            .map(|(handle, rb)| (rb.translation().dot(&axis), handle, rb))
+            .collect();
+        sorted.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+        sorted.into_iter().map(|(_, handle, rb)| (handle, rb))
+    }
+
+    /// Iterates through all the rigid-bodies on this set, sorted by increasing
+    /// [`RigidBody::user_data`].
+    ///
+    /// Rigid-bodies with the same user data are yielded in handle order. This sorts all the
+    /// rigid-bodies on each call so it costs `O(n log n)` time and allocates a vector of `n`
+    /// elements, where `n` is the number of rigid-bodies on this set.
+    pub fn iter_by_user_data(&self) -> impl Iterator<Item = (RigidBodyHandle, &RigidBody)> {
+        let mut sorted: Vec<_> = self.iter().collect();
+        sorted.sort_by_key(|(_, rb)| rb.user_data);
+        sorted.into_iter()
+    }
+
+    /// Iterates through the rigid-bodies inserted since the active set timestamp `timestamp`.
+    ///
+    /// A rigid-body is stamped with [`IslandManager::active_set_timestamp`] when the first
+    /// timestep following its insertion starts. Rigid-bodies inserted since the last timestep
+    /// are therefore not yielded until the next timestep is executed. Recording the timestamp
+    /// after each call lets the caller visit each new rigid-body exactly once.
+    pub fn bodies_inserted_since(
+        &self,
+        timestamp: u32,
+    ) -> impl Iterator<Item = RigidBodyHandle> + '_ {
+        self.iter()
+            .filter(move |(_, rb)| {
+                rb.ids
+                    .insertion_timestamp
+                    .map(|inserted_at| inserted_at >= timestamp)
+                    .unwrap_or(false)
+            })
+            .map(|(handle, _)| handle)
+    }
+
+    /// Iterates mutably through all the rigid-bodies on this set.
+    #[cfg(not(feature = "dev-remove-slow-accessors"))]
     pub fn iter_mut(&mut self) -> impl Iterator<Item = (RigidBodyHandle, &mut RigidBody)> {
         self.modified_bodies.clear();
         let modified_bodies = &mut self.modified_bodies;
@@ -206,6 +1613,432 @@ impl RigidBodySet {
         })
     }
 
+    /// Iterates through all the contact pairs involving one of the colliders attached to the
+    /// rigid-body with the given handle.
+    ///
+    /// Each contact pair is yielded together with the handle of the attached collider it involves.
+    fn contact_pairs_with<'a>(
+        &'a self,
+        handle: RigidBodyHandle,
+        narrow_phase: &'a NarrowPhase,
+    ) -> impl Iterator<Item = (ColliderHandle, &'a ContactPair)> + 'a {
+        self.get(handle)
+            .into_iter()
+            .flat_map(|rb| rb.colliders().iter().copied())
+            .flat_map(move |co_handle| {
+                narrow_phase
+                    .contacts_with(co_handle)
+                    .map(move |pair| (co_handle, pair))
+            })
+    }
+
+    /// The world-space solver contact points supporting the rigid-body with the given handle
+    /// against the direction `up`.
+    ///
+    /// A contact point is yielded if the contact normal pointing outward from the rigid-body
+    /// opposes `up`, e.g., the points where a box rests on the ground if `up` points upward.
+    /// Projecting these points on a plane orthogonal to `up` and computing their convex hull
+    /// yields the support polygon of the rigid-body.
+    pub fn support_points(
+        &self,
+        handle: RigidBodyHandle,
+        narrow_phase: &NarrowPhase,
+        up: Vector<Real>,
+    ) -> Vec<Point<Real>> {
+        let mut points = vec![];
+
+        for (co_handle, pair) in self.contact_pairs_with(handle, narrow_phase) {
+            // The manifold normals point from the first collider toward the second one.
+            let sign = if pair.collider1 == co_handle {
+                1.0
+            } else {
+                -1.0
+            };
+
+            for manifold in &pair.manifolds {
+                if (manifold.data.normal * sign).dot(&up) < 0.0 {
+                    points.extend(manifold.data.solver_contacts.iter().map(|c| c.point));
+                }
+            }
+        }
+
+        points
+    }
+
+    /// Iterates through all the rigid-bodies that started touching the rigid-body with the given
+    /// handle during the last timestep.
+    ///
+    /// A contact is considered new if one of its solver contacts did not exist during the previous
+    /// timestep. A rigid-body may be yielded more than once if it touches multiple colliders
+    /// attached to `handle`.
+    pub fn iter_new_contacting_bodies<'a>(
+        &'a self,
+        handle: RigidBodyHandle,
+        colliders: &'a ColliderSet,
+        narrow_phase: &'a NarrowPhase,
+    ) -> impl Iterator<Item = RigidBodyHandle> + 'a {
+        self.contact_pairs_with(handle, narrow_phase)
+            .filter(|(_, pair)| {
+                pair.manifolds
+                    .iter()
+                    .any(|m| m.data.solver_contacts.iter().any(|c| c.is_new))
+            })
+            .filter_map(move |(co_handle, pair)| {
+                let other = crate::utils::select_other((pair.collider1, pair.collider2), co_handle);
+                colliders.get(other)?.parent()
+            })
+    }
+
+    /// The world-space contact normal between the rigid-body with the given handle and each
+    /// rigid-body it touches.
+    ///
+    /// Each normal is the average of the normals of all the contact manifolds with at least one
+    /// solver contact between both rigid-bodies. It is oriented from the other rigid-body toward
+    /// the rigid-body with the given handle, e.g., it points upward for a body resting on the
+    /// ground.
+    pub fn contact_normals(
+        &self,
+        handle: RigidBodyHandle,
+        colliders: &ColliderSet,
+        narrow_phase: &NarrowPhase,
+    ) -> Vec<(RigidBodyHandle, Vector<Real>)> {
+        let mut normals: Vec<(RigidBodyHandle, Vector<Real>)> = vec![];
+
+        for (co_handle, pair) in self.contact_pairs_with(handle, narrow_phase) {
+            let other = crate::utils::select_other((pair.collider1, pair.collider2), co_handle);
+            let other_body = match colliders.get(other).and_then(|co| co.parent()) {
+                Some(other_body) => other_body,
+                None => continue,
+            };
+            // The manifold normals point from the first collider toward the second one.
+            let sign = if pair.collider1 == co_handle {
+                -1.0
+            } else {
+                1.0
+            };
+
+            for manifold in &pair.manifolds {
+                if manifold.data.solver_contacts.is_empty() {
+                    continue;
+                }
+
+                let normal = manifold.data.normal * sign;
+                match normals.iter_mut().find(|(body, _)| *body == other_body) {
+                    Some((_, sum)) => *sum += normal,
+                    None => normals.push((other_body, normal)),
+                }
+            }
+        }
+
+        for (_, normal) in &mut normals {
+            *normal = normal.try_normalize(1.0e-5).unwrap_or_else(Vector::zeros);
+        }
+
+        normals
+    }
+
+    /// The dynamic rigid-bodies touching the rigid-body with the given handle.
+    ///
+    /// Only contacts with at least one solver contact are taken into account. This is typically
+    /// used to find the dynamic rigid-bodies carried or pushed by a kinematic rigid-body.
+    pub fn kinematic_payload(
+        &self,
+        handle: RigidBodyHandle,
+        colliders: &ColliderSet,
+        narrow_phase: &NarrowPhase,
+    ) -> Vec<RigidBodyHandle> {
+        let mut touching = vec![];
+
+        if let Some(rb) = self.get(handle) {
+            super::island_manager::push_contacting_bodies(
+                &rb.colliders,
+                colliders,
+                narrow_phase,
+                &mut touching,
+            );
+        }
+
+        let mut payload = vec![];
+        for other in touching {
+            if self.get(other).map(|rb| rb.is_dynamic()) == Some(true) && !payload.contains(&other)
+            {
+                payload.push(other);
+            }
+        }
+
+        payload
+    }
+
+    /// The rigid-bodies that keep the rigid-body with the given handle awake.
+    ///
+    /// These are the neighbors, through contacts with at least one solver contact or through
+    /// joints, that propagate their awake state to the rigid-body with the given handle during
+    /// the active set update: the awake dynamic rigid-bodies, and the moving kinematic
+    /// rigid-bodies. Each neighbor is listed only once.
+    pub fn wake_sources(
+        &self,
+        handle: RigidBodyHandle,
+        colliders: &ColliderSet,
+        narrow_phase: &NarrowPhase,
+        impulse_joints: &ImpulseJointSet,
+        multibody_joints: &MultibodyJointSet,
+    ) -> Vec<RigidBodyHandle> {
+        let contact_neighbors = self
+            .contact_pairs_with(handle, narrow_phase)
+            .filter(|(_, pair)| {
+                pair.manifolds
+                    .iter()
+                    .any(|m| !m.data.solver_contacts.is_empty())
+            })
+            .filter_map(|(co_handle, pair)| {
+                let other = crate::utils::select_other((pair.collider1, pair.collider2), co_handle);
+                colliders.get(other)?.parent()
+            });
+        let joint_neighbors = self
+            .bodies_jointed_to(handle, impulse_joints)
+            .map(|(other, _)| other)
+            .chain(multibody_joints.attached_bodies(handle));
+
+        let mut sources = vec![];
+
+        for other in contact_neighbors.chain(joint_neighbors) {
+            let propagates_wake = match self.get(other).map(|rb| (rb, rb.body_type())) {
+                Some((rb, RigidBodyType::Dynamic)) => !rb.is_sleeping(),
+                Some((rb, RigidBodyType::KinematicPositionBased))
+                | Some((rb, RigidBodyType::KinematicVelocityBased)) => rb.is_moving(),
+                Some((_, RigidBodyType::Fixed)) | None => false,
+            };
+
+            if propagates_wake && !sources.contains(&other) {
+                sources.push(other);
+            }
+        }
+
+        sources
+    }
+
+    /// The deepest penetration between the colliders of the rigid-body with the given handle
+    /// and the colliders of the rigid-bodies it touches.
+    ///
+    /// Returns the penetrating rigid-body together with the penetration depth, which is always
+    /// positive. Returns `None` if there is no penetrating contact.
+    pub fn max_penetration(
+        &self,
+        handle: RigidBodyHandle,
+        colliders: &ColliderSet,
+        narrow_phase: &NarrowPhase,
+    ) -> Option<(RigidBodyHandle, Real)> {
+        let mut result: Option<(RigidBodyHandle, Real)> = None;
+
+        for (co_handle, pair) in self.contact_pairs_with(handle, narrow_phase) {
+            let other = crate::utils::select_other((pair.collider1, pair.collider2), co_handle);
+            let other_body = match colliders.get(other).and_then(|co| co.parent()) {
+                Some(other_body) => other_body,
+                None => continue,
+            };
+
+            for manifold in &pair.manifolds {
+                for pt in &manifold.points {
+                    if pt.dist < 0.0 && result.map(|(_, depth)| -pt.dist > depth).unwrap_or(true) {
+                        result = Some((other_body, -pt.dist));
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Iterates through all the rigid-bodies directly attached to the rigid-body with the given
+    /// handle by an impulse joint.
+    ///
+    /// Each neighbor is yielded together with the handle of the joint connecting it to `handle`.
+    pub fn bodies_jointed_to<'a>(
+        &'a self,
+        handle: RigidBodyHandle,
+        impulse_joints: &'a ImpulseJointSet,
+    ) -> impl Iterator<Item = (RigidBodyHandle, ImpulseJointHandle)> + 'a {
+        impulse_joints
+            .attached_joints(handle)
+            .map(move |(rb1, rb2, joint_handle, _)| {
+                (crate::utils::select_other((rb1, rb2), handle), joint_handle)
+            })
+    }
+
+    /// Does the rigid-body with the given handle have at least one contact manifold with
+    /// solver contacts?
+    ///
+    /// This stops at the first such contact manifold. Returns `false` if the handle is invalid.
+    pub fn has_any_contact(&self, handle: RigidBodyHandle, narrow_phase: &NarrowPhase) -> bool {
+        self.contact_pairs_with(handle, narrow_phase)
+            .any(|(_, pair)| {
+                pair.manifolds
+                    .iter()
+                    .any(|manifold| !manifold.data.solver_contacts.is_empty())
+            })
+    }
+
+    /// The sleeping dynamic rigid-bodies with at least one penetrating contact point.
+    ///
+    /// This typically happens when a fixed or kinematic rigid-body is teleported into a
+    /// sleeping rigid-body, which isn't woken up by contacts that didn't exist before. Waking
+    /// up the returned rigid-bodies lets the solver push them out. This iterates through all
+    /// the rigid-bodies of this set.
+    pub fn find_sleeping_overlaps(&self, narrow_phase: &NarrowPhase) -> Vec<RigidBodyHandle> {
+        self.iter()
+            .filter(|(_, rb)| rb.is_dynamic() && rb.is_sleeping())
+            .filter(|(handle, _)| {
+                self.contact_pairs_with(*handle, narrow_phase)
+                    .any(|(_, pair)| {
+                        pair.manifolds
+                            .iter()
+                            .any(|manifold| manifold.points.iter().any(|pt| pt.dist < 0.0))
+                    })
+            })
+            .map(|(handle, _)| handle)
+            .collect()
+    }
+
+    /// The distinct rigid-bodies with a collider whose AABB overlaps the AABB of a collider
+    /// attached to the rigid-body `handle`.
+    ///
+    /// These are the potential contacts found by the broad-phase during the last timestep,
+    /// including those involving sensors and those without any actual contact point. This is
+    /// coarser, and cheaper, than looking for actual contacts. The rigid-bodies are listed in
+    /// the order they are found. Returns an empty vector if `handle` is invalid.
+    pub fn overlapping_bodies(
+        &self,
+        handle: RigidBodyHandle,
+        colliders: &ColliderSet,
+        narrow_phase: &NarrowPhase,
+    ) -> Vec<RigidBodyHandle> {
+        let mut overlapping = vec![];
+        let rb = match self.get(handle) {
+            Some(rb) => rb,
+            None => return overlapping,
+        };
+
+        for co_handle in rb.colliders() {
+            let contacts = narrow_phase
+                .contacts_with(*co_handle)
+                .map(|pair| (pair.collider1, pair.collider2));
+            let intersections = narrow_phase
+                .intersections_with(*co_handle)
+                .map(|(co1, co2, _)| (co1, co2));
+
+            for pair in contacts.chain(intersections) {
+                let other = crate::utils::select_other(pair, *co_handle);
+                if let Some(other_body) = colliders.get(other).and_then(|co| co.parent()) {
+                    if other_body != handle && !overlapping.contains(&other_body) {
+                        overlapping.push(other_body);
+                    }
+                }
+            }
+        }
+
+        overlapping
+    }
+
+    /// Iterates through the distinct rigid-bodies in contact with the rigid-body `handle`.
+    ///
+    /// Only contact manifolds with at least one solver contact are taken into account, like
+    /// when contacts wake up rigid-bodies. Each rigid-body is yielded only once, in the order
+    /// its first contact is found. Nothing is yielded if `handle` is invalid.
+    pub fn contacting_bodies(
+        &self,
+        handle: RigidBodyHandle,
+        colliders: &ColliderSet,
+        narrow_phase: &NarrowPhase,
+    ) -> impl Iterator<Item = RigidBodyHandle> {
+        let mut contacting = vec![];
+
+        if let Some(rb) = self.get(handle) {
+            super::island_manager::push_contacting_bodies(
+                &rb.colliders,
+                colliders,
+                narrow_phase,
+                &mut contacting,
+            );
+        }
+
+        let mut distinct = Vec::with_capacity(contacting.len());
+        for other in contacting {
+            if !distinct.contains(&other) {
+                distinct.push(other);
+            }
+        }
+
+        distinct.into_iter()
+    }
+
+    /// The number of joints attached to the rigid-body with the given handle.
+    ///
+    /// This is the degree of this rigid-body on the impulse joint graph plus the number of
+    /// rigid-bodies it is directly attached to through multibody joints. Returns zero if the
+    /// handle is invalid.
+    pub fn joint_degree(
+        &self,
+        handle: RigidBodyHandle,
+        impulse_joints: &ImpulseJointSet,
+        multibody_joints: &MultibodyJointSet,
+    ) -> usize {
+        if !self.contains(handle) {
+            return 0;
+        }
+
+        impulse_joints.attached_joints(handle).count()
+            + multibody_joints.attached_bodies(handle).count()
+    }
+
+    /// The dynamic rigid-bodies that would be part of the same island as the rigid-body
+    /// `handle` given the current contacts and joints, without modifying any state.
+    ///
+    /// Two dynamic rigid-bodies are connected if their colliders have a contact manifold with
+    /// at least one solver contact, or if they are attached by a joint. Like the islands built
+    /// during a timestep, connections are not propagated through non-dynamic rigid-bodies.
+    /// Unlike these islands, this ignores the minimum island size and sleeping rigid-bodies are
+    /// included. The returned vector starts with `handle`, and is empty if `handle` is invalid
+    /// or doesn't identify a dynamic rigid-body.
+    pub fn predicted_island_of(
+        &self,
+        handle: RigidBodyHandle,
+        colliders: &ColliderSet,
+        narrow_phase: &NarrowPhase,
+        impulse_joints: &ImpulseJointSet,
+        multibody_joints: &MultibodyJointSet,
+    ) -> Vec<RigidBodyHandle> {
+        let mut island = vec![];
+        let mut visited = std::collections::HashSet::new();
+        let mut stack = vec![handle];
+
+        while let Some(handle) = stack.pop() {
+            let rb = match self.get(handle) {
+                Some(rb) if rb.is_dynamic() => rb,
+                _ => continue,
+            };
+
+            if !visited.insert(handle) {
+                continue;
+            }
+
+            island.push(handle);
+            super::island_manager::push_contacting_bodies(
+                &rb.colliders,
+                colliders,
+                narrow_phase,
+                &mut stack,
+            );
+
+            for (rb1, rb2, _, _) in impulse_joints.attached_joints(handle) {
+                stack.push(crate::utils::select_other((rb1, rb2), handle));
+            }
+
+            stack.extend(multibody_joints.attached_bodies(handle));
+        }
+
+        island
+    }
+
     /// Update colliders positions after rigid-bodies moved.
     ///
     /// When a rigid-body moves, the positions of the colliders attached to it need to be updated.
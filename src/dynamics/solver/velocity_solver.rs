@@ -158,7 +158,10 @@ impl VelocitySolver {
                 let mut new_vels = rb.vels;
                 new_vels.linvel += dvel.linear;
                 new_vels.angvel += dangvel;
-                new_vels = new_vels.apply_damping(params.dt, &rb.damping);
+                if !rb.is_coasting() {
+                    new_vels = new_vels.apply_damping(params.dt, &rb.damping);
+                }
+                new_vels = rb.masked_velocity(new_vels);
                 new_pos.next_position = new_vels.integrate(
                     params.dt,
                     &rb.pos.position,
@@ -230,6 +233,7 @@ impl VelocitySolver {
                 rb.vels.linvel += dvel.linear;
                 rb.vels.angvel += dangvel;
                 rb.vels = rb.vels.apply_damping(params.dt, &rb.damping);
+                rb.vels = rb.masked_velocity(rb.vels);
             }
         }
 
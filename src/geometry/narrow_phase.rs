@@ -8,9 +8,10 @@ use crate::dynamics::{
     RigidBodyType,
 };
 use crate::geometry::{
-    BroadPhasePairEvent, ColliderChanges, ColliderGraphIndex, ColliderHandle, ColliderPair,
-    ColliderSet, CollisionEvent, ContactData, ContactManifold, ContactManifoldData, ContactPair,
-    InteractionGraph, IntersectionPair, SolverContact, SolverFlags, TemporaryInteractionIndex,
+    BroadPhasePairEvent, Collider, ColliderChanges, ColliderGraphIndex, ColliderHandle,
+    ColliderPair, ColliderSet, CollisionEvent, ContactData, ContactManifold, ContactManifoldData,
+    ContactPair, InteractionGraph, IntersectionPair, SolverContact, SolverFlags,
+    TemporaryInteractionIndex,
 };
 use crate::math::{Real, Vector};
 use crate::pipeline::{
@@ -522,8 +523,9 @@ impl NarrowPhase {
                     // Emit an intersection lost event if we had an intersection before removing the edge.
                     if let Some(mut intersection) = intersection {
                         if intersection.intersecting {
-                            if (co1.flags.active_events | co2.flags.active_events)
-                                .contains(ActiveEvents::COLLISION_EVENTS)
+                            if (collider_active_events(co1, bodies)
+                                | collider_active_events(co2, bodies))
+                            .contains(ActiveEvents::COLLISION_EVENTS)
                             {
                                 intersection.emit_stop_event(
                                     bodies,
@@ -554,8 +556,9 @@ impl NarrowPhase {
                                 }
                             }
 
-                            if (co1.flags.active_events | co2.flags.active_events)
-                                .contains(ActiveEvents::COLLISION_EVENTS)
+                            if (collider_active_events(co1, bodies)
+                                | collider_active_events(co2, bodies))
+                            .contains(ActiveEvents::COLLISION_EVENTS)
                             {
                                 ctct.emit_stop_event(bodies, colliders, events);
                             }
@@ -714,6 +717,12 @@ impl NarrowPhase {
                     rb_type2 = bodies[co_parent2.handle].body_type;
                 }
 
+                // Colliders attached to disabled rigid-bodies don't interact.
+                if !parents_enabled(bodies, co1, co2) {
+                    edge.weight.intersecting = false;
+                    break 'emit_events;
+                }
+
                 // Filter based on the rigid-body types.
                 if !co1.flags.active_collision_types.test(rb_type1, rb_type2)
                     && !co2.flags.active_collision_types.test(rb_type1, rb_type2)
@@ -754,7 +763,8 @@ impl NarrowPhase {
                 break 'emit_events;
             }
 
-            let active_events = co1.flags.active_events | co2.flags.active_events;
+            let active_events =
+                collider_active_events(co1, bodies) | collider_active_events(co2, bodies);
 
             if active_events.contains(ActiveEvents::COLLISION_EVENTS)
                 && had_intersection != edge.weight.intersecting
@@ -815,6 +825,12 @@ impl NarrowPhase {
                     rb_type2 = bodies[co_parent2.handle].body_type;
                 }
 
+                // Colliders attached to disabled rigid-bodies don't interact.
+                if !parents_enabled(bodies, co1, co2) {
+                    pair.clear();
+                    break 'emit_events;
+                }
+
                 // Deal with contacts disabled between bodies attached by joints.
                 if let (Some(co_parent1), Some(co_parent2)) = (&co1.parent, &co2.parent) {
                     for (_, joint) in
@@ -985,7 +1001,8 @@ impl NarrowPhase {
                 break 'emit_events;
             }
 
-            let active_events = co1.flags.active_events | co2.flags.active_events;
+            let active_events =
+                collider_active_events(co1, bodies) | collider_active_events(co2, bodies);
 
             if pair.has_any_active_contact != had_any_active_contact {
                 if active_events.contains(ActiveEvents::COLLISION_EVENTS) {
@@ -1071,3 +1088,24 @@ impl NarrowPhase {
         }
     }
 }
+
+// The events enabled for the given collider, taking into account whether its parent
+// rigid-body has contact events enabled.
+#[inline(always)]
+fn collider_active_events(co: &Collider, bodies: &RigidBodySet) -> ActiveEvents {
+    match co.parent.and_then(|parent| bodies.get(parent.handle)) {
+        Some(rb) if !rb.is_contact_events_enabled() => ActiveEvents::empty(),
+        _ => co.flags.active_events,
+    }
+}
+
+// Are the parent rigid-bodies of both colliders, if any, enabled?
+#[inline(always)]
+fn parents_enabled(bodies: &RigidBodySet, co1: &Collider, co2: &Collider) -> bool {
+    [co1, co2].iter().all(|co| {
+        co.parent
+            .and_then(|parent| bodies.get(parent.handle))
+            .map(|rb| rb.is_enabled())
+            != Some(false)
+    })
+}
@@ -152,6 +152,9 @@ pub trait PhysicsHooks {
 
     /// Modifies the set of contacts seen by the constraints solver.
     fn modify_solver_contacts(&self, _context: &mut ContactModificationContext) {}
+
+    /// Called once per active island, right after the islands are computed.
+    fn preprocess_island(&self, _island_id: usize, _bodies: &[RigidBodyHandle]) {}
 }
 
 /// User-defined functions called by the physics engines during one timestep in order to customize its behavior.
@@ -235,6 +238,15 @@ pub trait PhysicsHooks: Send + Sync {
     ///
     /// The world-space contact normal can be modified in `context.normal`.
     fn modify_solver_contacts(&self, _context: &mut ContactModificationContext) {}
+
+    /// Called once per active island, right after the islands are computed and before
+    /// the constraints solver runs.
+    ///
+    /// The `bodies` slice contains the handles of all the dynamic rigid-bodies of the
+    /// island identified by `island_id`. Island ids are only valid for the current timestep.
+    /// This can be used to populate user-defined per-island data structures keyed on
+    /// Rapier's island partitioning.
+    fn preprocess_island(&self, _island_id: usize, _bodies: &[RigidBodyHandle]) {}
 }
 
 impl PhysicsHooks for () {
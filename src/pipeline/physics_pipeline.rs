@@ -5,7 +5,7 @@ use crate::counters::Counters;
 use crate::dynamics::IslandSolver;
 use crate::dynamics::{
     CCDSolver, ImpulseJointSet, IntegrationParameters, IslandManager, MultibodyJointSet,
-    RigidBodyPosition, RigidBodyType,
+    RigidBodyPosition, RigidBodyType, RigidBodyVelocity,
 };
 #[cfg(feature = "parallel")]
 use crate::dynamics::{JointGraphEdge, ParallelIslandSolver as IslandSolver};
@@ -154,6 +154,7 @@ impl PhysicsPipeline {
         colliders: &mut ColliderSet,
         impulse_joints: &mut ImpulseJointSet,
         multibody_joints: &mut MultibodyJointSet,
+        hooks: &dyn PhysicsHooks,
         events: &dyn EventHandler,
     ) {
         self.counters.stages.island_construction_time.resume();
@@ -166,6 +167,10 @@ impl PhysicsPipeline {
             multibody_joints,
             integration_parameters.min_island_size,
         );
+
+        for island_id in 0..islands.num_islands() {
+            hooks.preprocess_island(island_id, islands.active_island(island_id));
+        }
         self.counters.stages.island_construction_time.pause();
 
         if self.manifold_indices.len() < islands.num_islands() {
@@ -197,8 +202,16 @@ impl PhysicsPipeline {
             let rb = bodies.index_mut_internal(*handle);
             rb.mprops.update_world_mass_properties(&rb.pos.position);
             let effective_mass = rb.mprops.effective_mass();
-            rb.forces
-                .compute_effective_force_and_torque(&gravity, &effective_mass);
+
+            if let Some(coasting_vels) = &mut rb.coasting_vels {
+                *coasting_vels = rb.vels;
+                rb.forces.force = na::zero();
+                rb.forces.torque = na::zero();
+            } else {
+                rb.forces
+                    .compute_effective_force_and_torque(&gravity, &effective_mass);
+                rb.forces.torque += rb.upright_stabilization_torque();
+            }
         }
 
         for multibody in &mut multibody_joints.multibodies {
@@ -348,6 +361,16 @@ impl PhysicsPipeline {
         // Set the rigid-bodies and kinematic bodies to their final position.
         for handle in islands.iter_active_bodies() {
             let rb = bodies.index_mut_internal(handle);
+
+            if let Some(pose) = rb.held_pose {
+                rb.pos.next_position = pose;
+                rb.vels = RigidBodyVelocity::zero();
+            }
+
+            if let Some(vels) = rb.coasting_vels {
+                rb.vels = vels;
+            }
+
             rb.pos.position = rb.pos.next_position;
             rb.colliders
                 .update_positions(colliders, modified_colliders, &rb.pos.position);
@@ -446,6 +469,11 @@ impl PhysicsPipeline {
             multibody.1.forward_kinematics(bodies, true);
         }
 
+        for handle in islands.iter_active_bodies() {
+            let rb = bodies.index_mut_internal(handle);
+            rb.prev_pos = rb.pos.position;
+        }
+
         self.detect_collisions(
             integration_parameters,
             islands,
@@ -545,6 +573,7 @@ impl PhysicsPipeline {
                 colliders,
                 impulse_joints,
                 multibody_joints,
+                hooks,
                 events,
             );
 
@@ -603,6 +632,9 @@ impl PhysicsPipeline {
             rb.mprops.update_world_mass_properties(&rb.pos.position);
         }
 
+        bodies.timestep_count = bodies.timestep_count.wrapping_add(1);
+        bodies.update_settling_groups(islands);
+
         self.counters.step_completed();
     }
 }
@@ -610,12 +642,12 @@ impl PhysicsPipeline {
 #[cfg(test)]
 mod test {
     use crate::dynamics::{
-        CCDSolver, ImpulseJointSet, IntegrationParameters, IslandManager, RigidBodyBuilder,
-        RigidBodySet,
+        CCDSolver, FixedJointBuilder, ImpulseJointSet, IntegrationParameters, IslandManager,
+        RigidBody, RigidBodyBuilder, RigidBodyHandle, RigidBodySet, SlotStats,
     };
-    use crate::geometry::{BroadPhase, ColliderBuilder, ColliderSet, NarrowPhase};
-    use crate::math::Vector;
-    use crate::pipeline::PhysicsPipeline;
+    use crate::geometry::{BroadPhase, Collider, ColliderBuilder, ColliderSet, NarrowPhase};
+    use crate::math::{Isometry, Point, Real, Vector};
+    use crate::pipeline::{PhysicsPipeline, QueryPipeline};
     use crate::prelude::MultibodyJointSet;
 
     #[test]
@@ -710,6 +742,24 @@ mod test {
         );
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn island_count_after_deserialization() {
+        let islands = IslandManager::new();
+        let ser_islands = bincode::serialize(&islands).unwrap();
+        let islands2: IslandManager = bincode::deserialize(&ser_islands).unwrap();
+        assert_eq!(islands2.num_islands(), 0);
+        assert!(islands2.island_energies(&RigidBodySet::new()).is_empty());
+
+        // Simulate a snapshot taken before island managers were always initialized
+        // with a valid island boundary.
+        let mut empty_islands = IslandManager::new();
+        empty_islands.active_islands.clear();
+        let ser_islands = bincode::serialize(&empty_islands).unwrap();
+        let islands3: IslandManager = bincode::deserialize(&ser_islands).unwrap();
+        assert_eq!(islands3.num_islands(), 0);
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn rigid_body_removal_snapshot_handle_determinism() {
@@ -765,6 +815,256 @@ mod test {
         assert_eq!(h3a, h3b);
     }
 
+    #[test]
+    fn iter_sorted_is_stable_after_slot_reuse() {
+        let mut colliders = ColliderSet::new();
+        let mut impulse_joints = ImpulseJointSet::new();
+        let mut multibody_joints = MultibodyJointSet::new();
+        let mut islands = IslandManager::new();
+
+        let mut bodies = RigidBodySet::new();
+        let rb = RigidBodyBuilder::dynamic().build();
+        let h1 = bodies.insert(rb.clone());
+        let h2 = bodies.insert(rb.clone());
+        let h3 = bodies.insert(rb.clone());
+
+        bodies.remove(
+            h2,
+            &mut islands,
+            &mut colliders,
+            &mut impulse_joints,
+            &mut multibody_joints,
+            true,
+        );
+        let h4 = bodies.insert(rb.clone());
+        assert_eq!(h4.into_raw_parts().0, h2.into_raw_parts().0);
+
+        let sorted: Vec<_> = bodies.iter_sorted().map(|(h, _)| h).collect();
+        assert_eq!(sorted, vec![h1, h4, h3]);
+
+        let raw_parts: Vec<_> = sorted.iter().map(|h| h.into_raw_parts()).collect();
+        let mut expected = raw_parts.clone();
+        expected.sort();
+        assert_eq!(raw_parts, expected);
+
+        let sorted_again: Vec<_> = bodies.iter_sorted().map(|(h, _)| h).collect();
+        assert_eq!(sorted, sorted_again);
+    }
+
+    // A world with zero gravity and the default integration parameters.
+    struct TestWorld {
+        gravity: Vector<Real>,
+        params: IntegrationParameters,
+        pipeline: PhysicsPipeline,
+        islands: IslandManager,
+        broad_phase: BroadPhase,
+        narrow_phase: NarrowPhase,
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        impulse_joints: ImpulseJointSet,
+        multibody_joints: MultibodyJointSet,
+        ccd_solver: CCDSolver,
+    }
+
+    fn test_world() -> TestWorld {
+        TestWorld {
+            gravity: Vector::zeros(),
+            params: IntegrationParameters::default(),
+            pipeline: PhysicsPipeline::new(),
+            islands: IslandManager::new(),
+            broad_phase: BroadPhase::new(),
+            narrow_phase: NarrowPhase::new(),
+            bodies: RigidBodySet::new(),
+            colliders: ColliderSet::new(),
+            impulse_joints: ImpulseJointSet::new(),
+            multibody_joints: MultibodyJointSet::new(),
+            ccd_solver: CCDSolver::new(),
+        }
+    }
+
+    impl TestWorld {
+        fn step(&mut self) {
+            self.pipeline.step(
+                &self.gravity,
+                &self.params,
+                &mut self.islands,
+                &mut self.broad_phase,
+                &mut self.narrow_phase,
+                &mut self.bodies,
+                &mut self.colliders,
+                &mut self.impulse_joints,
+                &mut self.multibody_joints,
+                &mut self.ccd_solver,
+                &(),
+                &(),
+            );
+        }
+
+        // Inserts the rigid-body with a collider attached.
+        fn insert(
+            &mut self,
+            rb: impl Into<RigidBody>,
+            collider: impl Into<Collider>,
+        ) -> RigidBodyHandle {
+            let handle = self.bodies.insert(rb);
+            self.colliders
+                .insert_with_parent(collider, handle, &mut self.bodies);
+            handle
+        }
+
+        fn insert_ball(&mut self, rb: impl Into<RigidBody>) -> RigidBodyHandle {
+            self.insert(rb, ColliderBuilder::ball(0.5))
+        }
+
+        fn remove(&mut self, handle: RigidBodyHandle) -> Option<RigidBody> {
+            self.bodies.remove(
+                handle,
+                &mut self.islands,
+                &mut self.colliders,
+                &mut self.impulse_joints,
+                &mut self.multibody_joints,
+                true,
+            )
+        }
+    }
+
+    // Dynamic balls three units apart along the `x` axis, with the given velocities, after
+    // one timestep. With a minimum island size of 1, each ball is alone in its island.
+    fn spaced_balls(
+        min_island_size: usize,
+        linvels: &[Vector<Real>],
+    ) -> (TestWorld, Vec<RigidBodyHandle>) {
+        let mut world = test_world();
+        world.params.min_island_size = min_island_size;
+
+        let handles = linvels
+            .iter()
+            .enumerate()
+            .map(|(i, linvel)| {
+                world.insert_ball(
+                    RigidBodyBuilder::dynamic()
+                        .translation(Vector::x() * (i as Real * 3.0))
+                        .linvel(*linvel),
+                )
+            })
+            .collect();
+        world.step();
+
+        (world, handles)
+    }
+
+    #[test]
+    fn clear_modified_empties_iter_modified() {
+        let mut world = test_world();
+        let h = world.insert_ball(RigidBodyBuilder::dynamic());
+        world.step();
+
+        let bodies = &mut world.bodies;
+        bodies[h].set_linvel(Vector::x(), true);
+        assert_eq!(bodies.iter_modified().count(), 1);
+
+        bodies.clear_modified();
+        assert_eq!(bodies.iter_modified().count(), 0);
+
+        // The rigid-body is tracked again after its next modification.
+        bodies[h].set_linvel(Vector::y(), true);
+        assert_eq!(bodies.iter_modified().count(), 1);
+    }
+
+    #[test]
+    fn ccd_prevents_tunneling_through_thin_wall() {
+        for ccd_enabled in [false, true] {
+            let mut world = test_world();
+
+            #[cfg(feature = "dim2")]
+            let wall_shape = ColliderBuilder::cuboid(10.0, 0.05);
+            #[cfg(feature = "dim3")]
+            let wall_shape = ColliderBuilder::cuboid(10.0, 0.05, 10.0);
+            world.insert(RigidBodyBuilder::fixed(), wall_shape);
+
+            let h = world.insert(
+                RigidBodyBuilder::dynamic()
+                    .translation(Vector::y() * 2.0)
+                    .linvel(Vector::y() * -1000.0)
+                    .ccd_enabled(ccd_enabled),
+                ColliderBuilder::ball(0.1),
+            );
+
+            assert_eq!(
+                world
+                    .bodies
+                    .iter_ccd_enabled()
+                    .map(|(h, _)| h)
+                    .collect::<Vec<_>>(),
+                if ccd_enabled { vec![h] } else { vec![] }
+            );
+
+            for _ in 0..3 {
+                world.step();
+            }
+
+            assert_eq!(world.bodies[h].translation().y > 0.0, ccd_enabled);
+        }
+    }
+
+    #[test]
+    fn set_linvel_wakes_up_sleeping_body() {
+        let mut world = test_world();
+        let h = world.insert_ball(RigidBodyBuilder::dynamic());
+
+        world.step();
+        world.islands.sleep(&mut world.bodies, h);
+        assert!(world.bodies[h].is_sleeping());
+
+        world.bodies.set_linvel(h, Vector::x(), true);
+        world.step();
+
+        assert!(!world.bodies[h].is_sleeping());
+        assert!(world.islands.active_dynamic_bodies().contains(&h));
+        assert!(world.bodies[h].translation().x > 0.0);
+    }
+
+    #[test]
+    fn disabled_body_is_excluded_from_simulation() {
+        let mut world = test_world();
+        world.gravity = Vector::y() * -9.81;
+
+        let ground = world.insert(RigidBodyBuilder::fixed(), ColliderBuilder::ball(1.0));
+        let h = world.insert(
+            RigidBodyBuilder::dynamic().translation(Vector::y() * 1.5),
+            ColliderBuilder::ball(1.0),
+        );
+        let co1 = world.bodies[ground].colliders()[0];
+        let co2 = world.bodies[h].colliders()[0];
+        let has_active_contact = |world: &TestWorld| {
+            world
+                .narrow_phase
+                .contact_pair(co1, co2)
+                .map(|pair| pair.has_any_active_contact)
+                == Some(true)
+        };
+
+        world.step();
+        assert!(has_active_contact(&world));
+
+        world.bodies.set_enabled(h, false);
+        let pos = *world.bodies[h].position();
+
+        for _ in 0..5 {
+            world.step();
+        }
+
+        assert!(!world.islands.active_dynamic_bodies().contains(&h));
+        assert_eq!(*world.bodies[h].position(), pos);
+        assert!(!has_active_contact(&world));
+
+        world.bodies.set_enabled(h, true);
+        world.step();
+
+        assert!(world.islands.active_dynamic_bodies().contains(&h));
+        assert!(has_active_contact(&world));
+    }
+
     #[test]
     fn collider_removal_before_step() {
         let mut pipeline = PhysicsPipeline::new();
@@ -812,4 +1112,592 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn paused_body_holds_its_position() {
+        let mut world = test_world();
+        world.gravity = Vector::y() * -9.81;
+        let h = world.insert_ball(RigidBodyBuilder::dynamic());
+        world.bodies.set_paused(h, true);
+
+        for _ in 0..5 {
+            world.step();
+        }
+
+        assert_eq!(world.bodies[h].translation().y, 0.0);
+        assert!(world.islands.active_dynamic_bodies().is_empty());
+
+        world.bodies.set_paused(h, false);
+        world.step();
+
+        assert!(world.bodies[h].is_dynamic());
+        assert!(world.bodies[h].translation().y < 0.0);
+    }
+
+    #[test]
+    fn remove_batch_keeps_active_set_ids_consistent() {
+        let mut world = test_world();
+        world.gravity = Vector::y() * -9.81;
+        world.bodies.set_min_island_size(1);
+
+        let handles: Vec<_> = (0..30)
+            .map(|i| {
+                let builder = match i % 3 {
+                    0 => RigidBodyBuilder::fixed(),
+                    1 => RigidBodyBuilder::kinematic_velocity_based().linvel(Vector::x()),
+                    _ => RigidBodyBuilder::dynamic(),
+                };
+                world.insert_ball(builder.translation(Vector::x() * (i as Real * 3.0)))
+            })
+            .collect();
+
+        world.step();
+
+        let to_remove: Vec<_> = handles.iter().copied().step_by(2).collect();
+        let islands_before: Vec<_> = handles
+            .iter()
+            .map(|h| world.islands.island_of(&world.bodies, *h))
+            .collect();
+        let removed = world.bodies.remove_batch(
+            &to_remove,
+            &mut world.islands,
+            &mut world.colliders,
+            &mut world.impulse_joints,
+            &mut world.multibody_joints,
+        );
+        assert!(removed.iter().all(|rb| rb.is_some()));
+
+        let active_sets = [
+            world.islands.active_dynamic_bodies(),
+            world.islands.active_kinematic_bodies(),
+        ];
+        for active_set in active_sets {
+            for (i, handle) in active_set.iter().enumerate() {
+                assert!(!to_remove.contains(handle));
+                assert_eq!(world.bodies.active_set_id(*handle), Some(i));
+            }
+        }
+        assert_eq!(world.islands.active_dynamic_bodies().len(), 5);
+        assert_eq!(world.islands.active_kinematic_bodies().len(), 5);
+
+        for (handle, island) in handles.iter().zip(&islands_before) {
+            if !to_remove.contains(handle) {
+                assert_eq!(world.islands.island_of(&world.bodies, *handle), *island);
+            }
+        }
+
+        world.step();
+    }
+
+    #[test]
+    fn manually_slept_body_stays_asleep() {
+        let mut world = test_world();
+        world.gravity = Vector::y() * -9.81;
+        let h1 = world.insert_ball(RigidBodyBuilder::dynamic());
+        let h2 = world.insert_ball(RigidBodyBuilder::dynamic().translation(Vector::x() * 10.0));
+
+        world.step();
+        world.islands.sleep(&mut world.bodies, h1);
+
+        assert!(world.bodies[h1].is_sleeping());
+        assert_eq!(world.islands.active_dynamic_bodies(), &[h2]);
+        assert_eq!(world.bodies.active_set_id(h2), Some(0));
+
+        let pos = *world.bodies[h1].position();
+        for _ in 0..5 {
+            world.step();
+        }
+
+        assert!(world.bodies[h1].is_sleeping());
+        assert_eq!(*world.bodies[h1].position(), pos);
+        assert_eq!(world.islands.active_dynamic_bodies(), &[h2]);
+    }
+
+    #[test]
+    fn held_body_stays_at_its_pose() {
+        let mut world = test_world();
+        world.gravity = Vector::y() * -9.81;
+        let h = world.insert_ball(RigidBodyBuilder::dynamic());
+        let pose = Isometry::new(Vector::y() * 2.0, na::zero());
+        world.bodies.hold_at(h, pose);
+
+        for _ in 0..5 {
+            world.step();
+        }
+
+        assert_eq!(*world.bodies[h].position(), pose);
+        assert_eq!(*world.bodies[h].linvel(), Vector::zeros());
+
+        world.bodies.release_hold(h);
+        world.step();
+
+        assert!(world.bodies[h].translation().y < 2.0);
+    }
+
+    #[test]
+    fn coasting_body_keeps_its_velocity() {
+        let mut world = test_world();
+        world.gravity = Vector::y() * -9.81;
+
+        let linvel = Vector::x() * 2.0;
+        let h = world.insert_ball(
+            RigidBodyBuilder::dynamic()
+                .linvel(linvel)
+                .linear_damping(0.5),
+        );
+        world.bodies.set_coasting(h, true);
+
+        let num_steps = 10;
+        for _ in 0..num_steps {
+            world.step();
+        }
+
+        let expected = linvel * world.params.dt * num_steps as Real;
+        assert_eq!(*world.bodies[h].linvel(), linvel);
+        assert!((world.bodies[h].translation() - expected).norm() < 1.0e-4);
+    }
+
+    #[test]
+    fn settling_group_falls_asleep() {
+        let mut world = test_world();
+        let group = vec![
+            RigidBodyBuilder::dynamic().build(),
+            RigidBodyBuilder::dynamic()
+                .translation(Vector::y() * 2.0)
+                .build(),
+        ];
+        let handles = world.bodies.insert_settling_group(group, 3);
+
+        for i in 1..5 {
+            world.step();
+
+            // The group must leave the active set as soon as it falls asleep.
+            for handle in &handles {
+                let sleeping = world.bodies[*handle].is_sleeping();
+                assert_eq!(sleeping, i >= 3);
+                assert_eq!(
+                    world.islands.active_dynamic_bodies().contains(handle),
+                    !sleeping
+                );
+            }
+        }
+
+        assert!(world.islands.active_dynamic_bodies().is_empty());
+    }
+
+    #[test]
+    fn island_energies_after_removal_between_steps() {
+        let linvels = [Vector::x(), Vector::x() * 2.0, Vector::x() * 3.0];
+        let (mut world, handles) = spaced_balls(1, &linvels);
+        assert_eq!(world.islands.island_energies(&world.bodies).len(), 3);
+
+        // Each remaining body must still be alone in its island, with the same energy.
+        let check_islands = |world: &TestWorld, awake: &[RigidBodyHandle]| {
+            let energies = world.islands.island_energies(&world.bodies);
+            assert_eq!(energies.len(), 3);
+            assert_eq!(
+                energies.iter().filter(|e| **e == 0.0).count(),
+                3 - awake.len()
+            );
+
+            for handle in awake {
+                let island_id = world.islands.island_of(&world.bodies, *handle).unwrap();
+                assert_eq!(world.islands.active_island(island_id), &[*handle]);
+                assert_eq!(energies[island_id], world.bodies[*handle].kinetic_energy());
+            }
+        };
+
+        world.remove(handles[0]);
+        check_islands(&world, &handles[1..]);
+
+        world.islands.sleep(&mut world.bodies, handles[1]);
+        check_islands(&world, &handles[2..]);
+    }
+
+    #[test]
+    fn island_centroid_after_sleep_between_steps() {
+        let (mut world, handles) = spaced_balls(1, &[Vector::zeros(); 2]);
+        let slept_island = world.islands.island_of(&world.bodies, handles[0]).unwrap();
+        let awake_island = world.islands.island_of(&world.bodies, handles[1]).unwrap();
+        world.islands.sleep(&mut world.bodies, handles[0]);
+
+        let islands = &world.islands;
+        assert_eq!(islands.island_centroid(&world.bodies, slept_island), None);
+        assert_eq!(
+            islands.island_centroid(&world.bodies, awake_island),
+            Some((Point::from(*world.bodies[handles[1]].translation()), 0.0))
+        );
+        assert_eq!(islands.island_centroid(&world.bodies, 2), None);
+    }
+
+    #[test]
+    fn apply_island_velocity_after_sleep_between_steps() {
+        let (mut world, handles) = spaced_balls(1, &[Vector::zeros(); 2]);
+        let slept_island = world.islands.island_of(&world.bodies, handles[0]).unwrap();
+        let awake_island = world.islands.island_of(&world.bodies, handles[1]).unwrap();
+        world.islands.sleep(&mut world.bodies, handles[0]);
+
+        let bodies = &mut world.bodies;
+        world
+            .islands
+            .apply_island_velocity(bodies, slept_island, Vector::x());
+        world
+            .islands
+            .apply_island_velocity(bodies, awake_island, Vector::y());
+
+        assert!(bodies[handles[0]].is_sleeping());
+        assert_eq!(*bodies[handles[0]].linvel(), Vector::zeros());
+        assert_eq!(*bodies[handles[1]].linvel(), Vector::y());
+    }
+
+    #[test]
+    fn island_mean_velocity_after_removal_between_steps() {
+        let linvels = [Vector::x(), Vector::x() * 2.0, Vector::x() * 3.0];
+        let (mut world, handles) = spaced_balls(128, &linvels);
+        let mean_velocity = |world: &TestWorld| world.islands.island_mean_velocity(&world.bodies);
+        assert_eq!(mean_velocity(&world), vec![Vector::x() * 2.0]);
+
+        world.remove(handles[2]);
+        assert_eq!(mean_velocity(&world), vec![Vector::x() * 1.5]);
+
+        world.islands.sleep(&mut world.bodies, handles[1]);
+        world.islands.sleep(&mut world.bodies, handles[0]);
+        assert_eq!(mean_velocity(&world), vec![Vector::zeros()]);
+    }
+
+    #[test]
+    fn settling_islands_after_sleep_between_steps() {
+        let (mut world, handles) = spaced_balls(1, &[Vector::zeros(), Vector::x() * 10.0]);
+        let resting_island = world.islands.island_of(&world.bodies, handles[0]).unwrap();
+        assert_eq!(
+            world.islands.settling_islands(&world.bodies),
+            vec![resting_island]
+        );
+
+        world.islands.sleep(&mut world.bodies, handles[0]);
+        assert!(world.islands.settling_islands(&world.bodies).is_empty());
+
+        world.remove(handles[1]);
+        assert!(world.islands.settling_islands(&world.bodies).is_empty());
+    }
+
+    #[test]
+    fn reserved_handles_are_not_free_slots() {
+        let mut world = test_world();
+        world.bodies = RigidBodySet::with_capacity(8);
+        let bodies = &mut world.bodies;
+
+        for _ in 0..5 {
+            bodies.insert(RigidBodyBuilder::dynamic().build());
+        }
+        let reserved = bodies.reserve_handles(2);
+
+        assert_eq!(bodies.free_slot_count(), 1);
+        assert_eq!(
+            bodies.slot_stats(),
+            SlotStats {
+                used: 5,
+                free: 1,
+                reserved: 2,
+                capacity: 8,
+            }
+        );
+        assert_eq!(bodies.fragmentation_ratio(), 1.0 / 8.0);
+
+        bodies.reserve(3);
+        assert_eq!(bodies.free_slot_count(), 3);
+
+        assert!(bodies.fill_reserved(reserved[0], RigidBodyBuilder::dynamic()));
+        assert_eq!(bodies.slot_stats().reserved, 1);
+        assert_eq!(bodies.free_slot_count(), 3);
+
+        bodies.clear(
+            &mut world.islands,
+            &mut world.colliders,
+            &mut world.impulse_joints,
+            &mut world.multibody_joints,
+            true,
+        );
+        let stats = bodies.slot_stats();
+        assert_eq!(stats.reserved, 0);
+        assert_eq!(stats.free, stats.capacity);
+        assert!(!bodies.fill_reserved(reserved[1], RigidBodyBuilder::dynamic()));
+    }
+
+    #[test]
+    fn upright_stabilization_converges_without_overshoot() {
+        let mut world = test_world();
+
+        #[cfg(feature = "dim2")]
+        let (tilt, shape) = (0.5, ColliderBuilder::cuboid(2.0, 0.5));
+        #[cfg(feature = "dim3")]
+        let (tilt, shape) = (Vector::x() * 0.5, ColliderBuilder::cuboid(2.0, 0.5, 1.0));
+
+        // A heavy body, to check the restoring torque accounts for its angular inertia.
+        let h = world.insert(
+            RigidBodyBuilder::dynamic().rotation(tilt),
+            shape.density(100.0),
+        );
+        world
+            .bodies
+            .set_upright_stabilization(h, Some((Vector::y(), 25.0)));
+
+        let tilt =
+            |world: &TestWorld| (world.bodies[h].position() * Vector::y() - Vector::y()).norm();
+        let mut prev_tilt = tilt(&world);
+
+        for _ in 0..180 {
+            world.step();
+
+            let new_tilt = tilt(&world);
+            assert!(new_tilt <= prev_tilt);
+            prev_tilt = new_tilt;
+        }
+
+        assert!(prev_tilt < 1.0e-4);
+    }
+
+    #[test]
+    fn explosion_pushes_nearby_dynamic_bodies_away() {
+        let mut world = test_world();
+        let mut query_pipeline = QueryPipeline::new();
+
+        let bodies_desc = [
+            (RigidBodyBuilder::dynamic(), Vector::x() * 2.0),
+            (RigidBodyBuilder::dynamic(), -Vector::x() * 4.0),
+            (RigidBodyBuilder::dynamic(), Vector::x() * 10.0),
+            (RigidBodyBuilder::fixed(), Vector::y() * 2.0),
+            (
+                RigidBodyBuilder::kinematic_velocity_based(),
+                -Vector::y() * 2.0,
+            ),
+        ];
+        let handles: Vec<_> = bodies_desc
+            .into_iter()
+            .map(|(builder, translation)| world.insert_ball(builder.translation(translation)))
+            .collect();
+
+        world.step();
+        query_pipeline.update(&world.islands, &world.bodies, &world.colliders);
+        let bodies = &mut world.bodies;
+
+        // A non-finite center must not affect anything.
+        bodies.apply_explosion(
+            Point::from(Vector::repeat(Real::NAN)),
+            5.0,
+            10.0,
+            &world.colliders,
+            &query_pipeline,
+        );
+        assert!(bodies.iter().all(|(_, rb)| *rb.linvel() == Vector::zeros()));
+
+        bodies.apply_explosion(
+            Point::origin(),
+            5.0,
+            10.0,
+            &world.colliders,
+            &query_pipeline,
+        );
+
+        // The impulse points away from the center and decreases linearly with the distance
+        // between the center and the collider AABB: 1.5 and 3.5 here.
+        let mass = bodies[handles[0]].mass();
+        let vel1 = *bodies[handles[0]].linvel();
+        let vel2 = *bodies[handles[1]].linvel();
+        assert!((vel1 - Vector::x() * 10.0 * 0.7 / mass).norm() < 1.0e-5);
+        assert!((vel2 + Vector::x() * 10.0 * 0.3 / mass).norm() < 1.0e-5);
+
+        // Out of range and non-dynamic rigid-bodies are left untouched.
+        for handle in &handles[2..] {
+            assert_eq!(*bodies[*handle].linvel(), Vector::zeros());
+        }
+    }
+
+    #[test]
+    fn migrated_bodies_keep_their_age_and_shared_joints() {
+        let (mut world, handles) = spaced_balls(128, &[Vector::zeros(); 4]);
+        let mut dst_bodies = RigidBodySet::new();
+        let mut dst_colliders = ColliderSet::new();
+        let mut dst_impulse_joints = ImpulseJointSet::new();
+        let mut dst_multibody_joints = MultibodyJointSet::new();
+
+        world
+            .impulse_joints
+            .insert(handles[0], handles[1], FixedJointBuilder::new(), true);
+        world
+            .impulse_joints
+            .insert(handles[2], handles[3], FixedJointBuilder::new(), true);
+        world.bodies.acquire_awake_lease(handles[0]).unwrap();
+
+        let positions: Vec<_> = handles
+            .iter()
+            .map(|h| *world.bodies[*h].position())
+            .collect();
+
+        // Both ends of the first joint are migrated together.
+        let mut dst_handles = world.bodies.migrate_bodies_to(
+            &handles[..2],
+            &mut world.islands,
+            &mut world.colliders,
+            &mut world.impulse_joints,
+            &mut world.multibody_joints,
+            &mut dst_bodies,
+            &mut dst_colliders,
+            &mut dst_impulse_joints,
+            &mut dst_multibody_joints,
+        );
+        // Only one end of the second joint is migrated.
+        dst_handles.push(world.bodies.migrate_body_to(
+            handles[2],
+            &mut world.islands,
+            &mut world.colliders,
+            &mut world.impulse_joints,
+            &mut world.multibody_joints,
+            &mut dst_bodies,
+            &mut dst_colliders,
+            &mut dst_impulse_joints,
+            &mut dst_multibody_joints,
+        ));
+
+        for (i, dst_handle) in dst_handles.iter().enumerate() {
+            assert!(world.bodies.get(handles[i]).is_none());
+
+            let rb = &dst_bodies[dst_handle.unwrap()];
+            assert_eq!(*rb.position(), positions[i]);
+            assert_eq!(dst_bodies.age_steps(dst_handle.unwrap()), Some(1));
+            assert!(!rb.has_awake_leases());
+            assert_eq!(rb.colliders().len(), 1);
+        }
+
+        assert_eq!(dst_impulse_joints.len(), 1);
+        let (rb1, rb2, _, _) = dst_impulse_joints
+            .attached_joints(dst_handles[0].unwrap())
+            .next()
+            .unwrap();
+        assert_eq!(
+            (rb1, rb2),
+            (dst_handles[0].unwrap(), dst_handles[1].unwrap())
+        );
+        assert_eq!(
+            dst_impulse_joints
+                .attached_joints(dst_handles[2].unwrap())
+                .count(),
+            0
+        );
+
+        assert_eq!(world.impulse_joints.len(), 0);
+        assert_eq!(world.impulse_joints.attached_joints(handles[3]).count(), 0);
+        assert_eq!(world.bodies.len(), 1);
+        assert_eq!(world.colliders.len(), 1);
+        assert_eq!(dst_bodies.len(), 3);
+        assert_eq!(dst_colliders.len(), 3);
+    }
+
+    #[test]
+    fn sleeping_steps_are_excluded_from_the_age_of_opted_out_bodies() {
+        let (mut world, handles) = spaced_balls(128, &[Vector::zeros(); 2]);
+        world.bodies[handles[1]].set_ages_while_sleeping(false);
+
+        world.islands.sleep_many(&mut world.bodies, &handles);
+        for _ in 0..3 {
+            world.step();
+        }
+
+        assert_eq!(world.bodies.age_steps(handles[0]), Some(4));
+        assert_eq!(world.bodies.age_steps(handles[1]), Some(1));
+
+        for handle in &handles {
+            world.islands.wake_up(&mut world.bodies, *handle, true);
+        }
+        world.step();
+
+        assert_eq!(world.bodies.age_steps(handles[0]), Some(5));
+        assert_eq!(world.bodies.age_steps(handles[1]), Some(2));
+    }
+
+    #[test]
+    fn island_changes_are_only_tracked_when_enabled() {
+        let (mut world, handles) = spaced_balls(1, &[Vector::zeros(); 2]);
+        world.step();
+        assert_eq!(world.bodies.steps_since_island_change(handles[0]), Some(0));
+
+        world.islands.set_track_island_changes(true);
+        for _ in 0..3 {
+            world.step();
+        }
+        assert_eq!(world.bodies.steps_since_island_change(handles[0]), Some(2));
+
+        // The two balls are now part of the same island.
+        world
+            .impulse_joints
+            .insert(handles[0], handles[1], FixedJointBuilder::new(), true);
+        world.step();
+        assert_eq!(world.bodies.steps_since_island_change(handles[0]), Some(3));
+        assert_eq!(world.bodies.steps_since_island_change(handles[1]), Some(0));
+    }
+
+    #[test]
+    fn detached_joints_wake_up_their_other_bodies() {
+        let (mut world, handles) = spaced_balls(128, &[Vector::zeros(); 3]);
+        world
+            .impulse_joints
+            .insert(handles[0], handles[1], FixedJointBuilder::new(), true);
+        world
+            .multibody_joints
+            .insert(handles[1], handles[2], FixedJointBuilder::new(), true);
+        world.step();
+        world.islands.sleep_many(&mut world.bodies, &handles);
+
+        world.bodies.detach_joints(
+            handles[1],
+            &mut world.impulse_joints,
+            &mut world.multibody_joints,
+        );
+
+        assert_eq!(world.impulse_joints.len(), 0);
+        assert_eq!(
+            world.multibody_joints.attached_joints(handles[1]).count(),
+            0
+        );
+        for handle in &handles {
+            assert!(!world.bodies[*handle].is_sleeping());
+        }
+    }
+
+    #[test]
+    fn steps_since_contact_tracks_solver_contacts() {
+        let mut world = test_world();
+        world.insert(RigidBodyBuilder::fixed(), ColliderBuilder::ball(1.0));
+        let resting =
+            world.insert_ball(RigidBodyBuilder::dynamic().translation(Vector::y() * 1.45));
+        let floating =
+            world.insert_ball(RigidBodyBuilder::dynamic().translation(Vector::x() * 10.0));
+
+        for _ in 0..3 {
+            world.step();
+        }
+
+        assert_eq!(world.bodies.airborne_steps(resting), Some(0));
+        assert_eq!(world.bodies.airborne_steps(floating), Some(3));
+    }
+
+    #[test]
+    fn inserted_like_body_keeps_the_template_mass() {
+        let mut world = test_world();
+        let template = world.insert(
+            RigidBodyBuilder::dynamic().additional_mass(2.0),
+            ColliderBuilder::ball(0.5).density(3.0),
+        );
+        world.step();
+
+        let pose = Isometry::new(Vector::x() * 5.0, na::zero());
+        let h = world.bodies.insert_like(template, pose).unwrap();
+        world.step();
+
+        let (rb, template) = (&world.bodies[h], &world.bodies[template]);
+        assert!(rb.colliders().is_empty());
+        assert_eq!(rb.mass(), template.mass());
+        assert_eq!(rb.mass_properties(), template.mass_properties());
+        assert_eq!(rb.mprops.world_com, Point::from(Vector::x() * 5.0));
+    }
 }
@@ -48,6 +48,8 @@ pub(crate) fn handle_user_changes_to_rigid_bodies(
         UpdateActiveDynamicSetId,
     }
 
+    let timestep_count = bodies.timestep_count;
+
     for handle in modified_bodies {
         let mut final_action = None;
 
@@ -65,6 +67,24 @@ pub(crate) fn handle_user_changes_to_rigid_bodies(
             // The body's status changed. We need to make sure
             // it is on the correct active set.
             if let Some(islands) = islands.as_deref_mut() {
+                // Record when the body was seen for the first time after its insertion.
+                if ids.insertion_timestamp.is_none() {
+                    ids.insertion_timestamp = Some(islands.active_set_timestamp());
+                }
+
+                // Disabled bodies are not part of any active set.
+                if changes.contains(RigidBodyChanges::ENABLED_OR_DISABLED) && !rb.enabled {
+                    if islands.active_dynamic_set.get(ids.active_set_id) == Some(handle) {
+                        islands.active_dynamic_set.swap_remove(ids.active_set_id);
+                        final_action =
+                            Some((FinalAction::UpdateActiveDynamicSetId, ids.active_set_id));
+                    } else if islands.active_kinematic_set.get(ids.active_set_id) == Some(handle) {
+                        islands.active_kinematic_set.swap_remove(ids.active_set_id);
+                        final_action =
+                            Some((FinalAction::UpdateActiveKinematicSetId, ids.active_set_id));
+                    }
+                }
+
                 if changes.contains(RigidBodyChanges::TYPE) {
                     match rb.body_type {
                         RigidBodyType::Dynamic => {
@@ -78,7 +98,9 @@ pub(crate) fn handle_user_changes_to_rigid_bodies(
                             }
 
                             // Add to the active dynamic set.
-                            activation.wake_up(true);
+                            if rb.enabled {
+                                activation.wake_up(true);
+                            }
                             // Make sure the sleep change flag is set (even if for some
                             // reasons the rigid-body was already awake) to make
                             // sure the code handling sleeping change adds the body to
@@ -97,7 +119,10 @@ pub(crate) fn handle_user_changes_to_rigid_bodies(
                             }
 
                             // Add to the active kinematic set.
-                            if islands.active_kinematic_set.get(ids.active_set_id) != Some(handle) {
+                            if rb.enabled
+                                && islands.active_kinematic_set.get(ids.active_set_id)
+                                    != Some(handle)
+                            {
                                 ids.active_set_id = islands.active_kinematic_set.len();
                                 islands.active_kinematic_set.push(*handle);
                             }
@@ -106,14 +131,17 @@ pub(crate) fn handle_user_changes_to_rigid_bodies(
                     }
                 }
 
-                // Update the positions of the colliders.
+                // Update the positions of the colliders. This also triggers the narrow-phase
+                // update of their contacts when the body is enabled or disabled.
                 if changes.contains(RigidBodyChanges::POSITION)
                     || changes.contains(RigidBodyChanges::COLLIDERS)
+                    || changes.contains(RigidBodyChanges::ENABLED_OR_DISABLED)
                 {
                     rb.colliders
                         .update_positions(colliders, modified_colliders, &rb.pos.position);
 
                     if rb.is_kinematic()
+                        && rb.enabled
                         && islands.active_kinematic_set.get(ids.active_set_id) != Some(handle)
                     {
                         ids.active_set_id = islands.active_kinematic_set.len();
@@ -126,6 +154,7 @@ pub(crate) fn handle_user_changes_to_rigid_bodies(
                 if changes.contains(RigidBodyChanges::SLEEP)
                     && !activation.sleeping // May happen if the body was put to sleep manually.
                     && rb.is_dynamic() // Only dynamic bodies are in the active dynamic set.
+                    && rb.enabled
                     && islands.active_dynamic_set.get(ids.active_set_id) != Some(handle)
                 {
                     ids.active_set_id = islands.active_dynamic_set.len(); // This will handle the case where the activation_channel contains duplicates.
@@ -168,6 +197,7 @@ pub(crate) fn handle_user_changes_to_rigid_bodies(
             rb.changes = RigidBodyChanges::empty();
             rb.ids = ids;
             rb.activation = activation;
+            rb.update_asleep_steps(timestep_count);
         }
 
         // Adjust some ids, if needed.